@@ -0,0 +1,346 @@
+//! The listings of `list-batch`, read from a CSV or JSON file
+//!
+//! Every row names one NFT, by its sub id in the network's collection or by its asset id, with
+//! its price. The NFTs are sent to their predicates in as few transactions as the input and
+//! output limits of the chain allow
+
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{Address, AssetId, ContractId, ScriptTransactionBuilder, TxPolicies, WalletUnlocked},
+    types::{errors::Error, Bytes32},
+};
+use nullstate_sdk::{collection::get_asset_id, fee::FeePolicy, listing::ListingParams, serde_hex};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The columns of a batch file, `sub_id` or `asset_id` and `price` are required
+pub const COLUMNS: [&str; 5] = ["sub_id", "asset_id", "price", "ask_asset", "expiry"];
+
+/// Base asset inputs left for the fee of every funding transaction
+const FEE_INPUTS: usize = 8;
+
+/// The NFT a row lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchNft {
+    /// A token of the collection in the network's manifest
+    SubId(Bytes32),
+    AssetId(AssetId),
+}
+
+/// One row of a batch file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchEntry {
+    pub nft: BatchNft,
+    pub price: u64,
+    /// The base asset when omitted
+    pub ask_asset: Option<AssetId>,
+    /// Unix timestamp in seconds recorded in the order, see `OrderMetadata::expires_at`
+    pub expiry: Option<u64>,
+}
+
+/// A row as written in either format, before checking which NFT it names
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchRow {
+    #[serde(default, with = "serde_hex::option")]
+    sub_id: Option<Bytes32>,
+    #[serde(default, with = "serde_hex::option")]
+    asset_id: Option<AssetId>,
+    price: u64,
+    #[serde(default, with = "serde_hex::option")]
+    ask_asset: Option<AssetId>,
+    #[serde(default)]
+    expiry: Option<u64>,
+}
+
+/// Why a batch could not be read or turned into listings
+#[derive(Debug)]
+pub enum BatchError {
+    Io(PathBuf, std::io::Error),
+    Json(serde_json::Error),
+    /// The file is neither `.csv` nor `.json`
+    UnknownFormat(PathBuf),
+    /// A CSV row could not be read, rows count from 1 after the header
+    Csv {
+        row: usize,
+        reason: String,
+    },
+    /// A row names both or neither of a sub id and an asset id
+    AmbiguousNft(usize),
+    /// Rows name sub ids but no collection is deployed on the network
+    NoCollection,
+    /// The same NFT is listed twice
+    Duplicate(AssetId),
+    Empty,
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "cannot read {}: {e}", path.display()),
+            Self::Json(e) => write!(f, "invalid batch JSON: {e}"),
+            Self::UnknownFormat(path) => write!(
+                f,
+                "cannot tell the format of {}, use a .csv or .json file",
+                path.display()
+            ),
+            Self::Csv { row, reason } => write!(f, "row {row}: {reason}"),
+            Self::AmbiguousNft(row) => {
+                write!(f, "row {row}: give exactly one of sub_id and asset_id")
+            }
+            Self::NoCollection => write!(
+                f,
+                "rows name sub ids but no collection is deployed, run deploy-collection first"
+            ),
+            Self::Duplicate(asset_id) => write!(f, "{asset_id:#x} is listed twice"),
+            Self::Empty => write!(f, "the batch lists nothing"),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl BatchRow {
+    fn entry(self, row: usize) -> Result<BatchEntry, BatchError> {
+        let nft = match (self.sub_id, self.asset_id) {
+            (Some(sub_id), None) => BatchNft::SubId(sub_id),
+            (None, Some(asset_id)) => BatchNft::AssetId(asset_id),
+            _ => return Err(BatchError::AmbiguousNft(row)),
+        };
+
+        Ok(BatchEntry {
+            nft,
+            price: self.price,
+            ask_asset: self.ask_asset,
+            expiry: self.expiry,
+        })
+    }
+}
+
+/// Reads the batch at `path`, its format is told by its extension
+pub fn read_batch(path: &Path) -> Result<Vec<BatchEntry>, BatchError> {
+    let parse = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => parse_csv,
+        Some("json") => parse_json,
+        _ => return Err(BatchError::UnknownFormat(path.to_path_buf())),
+    };
+    let contents = fs::read_to_string(path).map_err(|e| BatchError::Io(path.to_path_buf(), e))?;
+
+    parse(&contents)
+}
+
+/// Reads a JSON array of rows, each an object keyed by the column names
+pub fn parse_json(json: &str) -> Result<Vec<BatchEntry>, BatchError> {
+    let rows: Vec<BatchRow> = serde_json::from_str(json).map_err(BatchError::Json)?;
+    let entries = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| row.entry(index + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    non_empty(entries)
+}
+
+/// Reads a CSV file whose header names the columns, empty cells are omitted values
+///
+/// Cells may be quoted, see `split_csv_line`
+pub fn parse_csv(csv: &str) -> Result<Vec<BatchEntry>, BatchError> {
+    let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+    let header = match lines.next() {
+        Some(header) => {
+            split_csv_line(header).map_err(|reason| BatchError::Csv { row: 0, reason })?
+        }
+        None => return Err(BatchError::Empty),
+    };
+    if let Some(column) = header
+        .iter()
+        .find(|column| !COLUMNS.contains(&column.as_str()))
+    {
+        return Err(BatchError::Csv {
+            row: 0,
+            reason: format!("unknown column `{column}`"),
+        });
+    }
+
+    let entries = lines
+        .enumerate()
+        .map(|(index, line)| {
+            let row = index + 1;
+            let cells = split_csv_line(line).map_err(|reason| BatchError::Csv { row, reason })?;
+            if cells.len() != header.len() {
+                return Err(BatchError::Csv {
+                    row,
+                    reason: format!("{} fields but {} columns", cells.len(), header.len()),
+                });
+            }
+            let cell = |column: &str| {
+                header
+                    .iter()
+                    .position(|name| *name == column)
+                    .map(|index| cells[index].as_str())
+                    .filter(|cell| !cell.is_empty())
+            };
+
+            BatchRow {
+                sub_id: parse_cell(row, "sub_id", cell("sub_id"))?,
+                asset_id: parse_cell(row, "asset_id", cell("asset_id"))?,
+                price: parse_cell(row, "price", cell("price"))?.ok_or_else(|| BatchError::Csv {
+                    row,
+                    reason: "missing price".to_string(),
+                })?,
+                ask_asset: parse_cell(row, "ask_asset", cell("ask_asset"))?,
+                expiry: parse_cell(row, "expiry", cell("expiry"))?,
+            }
+            .entry(row)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    non_empty(entries)
+}
+
+/// The cells of a CSV line, trimmed unless quoted
+///
+/// A quoted cell may hold commas and writes a quote as two quotes. Quoted cells cannot span
+/// lines, so an unterminated quote is an error, as is a quote within an unquoted cell
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut cells = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut cell = String::new();
+        let quoted = chars.next_if_eq(&'"').is_some();
+        if quoted {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => cell.push('"'),
+                    Some('"') => break,
+                    Some(c) => cell.push(c),
+                    None => return Err("unterminated quoted field".to_string()),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        }
+
+        let end = loop {
+            match chars.next() {
+                None => break None,
+                Some(',') => break Some(','),
+                Some(c) if quoted => return Err(format!("`{c}` after a quoted field")),
+                Some('"') => return Err("quote within an unquoted field".to_string()),
+                Some(c) => cell.push(c),
+            }
+        };
+        cells.push(if quoted {
+            cell
+        } else {
+            cell.trim().to_string()
+        });
+        if end.is_none() {
+            return Ok(cells);
+        }
+    }
+}
+
+fn parse_cell<T: FromStr>(
+    row: usize,
+    column: &str,
+    cell: Option<&str>,
+) -> Result<Option<T>, BatchError> {
+    cell.map(|cell| {
+        cell.parse().map_err(|_| BatchError::Csv {
+            row,
+            reason: format!("invalid {column} `{cell}`"),
+        })
+    })
+    .transpose()
+}
+
+fn non_empty(entries: Vec<BatchEntry>) -> Result<Vec<BatchEntry>, BatchError> {
+    if entries.is_empty() {
+        return Err(BatchError::Empty);
+    }
+    Ok(entries)
+}
+
+/// The terms every listing of a batch shares
+#[derive(Debug, Clone)]
+pub struct BatchTerms {
+    pub receiver: Address,
+    pub treasury: Address,
+    /// Paid in the ask asset of each listing
+    pub fee: FeePolicy,
+    /// The ask asset of rows that give none
+    pub base_asset: AssetId,
+    /// The collection sub ids are tokens of
+    pub collection: Option<ContractId>,
+}
+
+impl BatchTerms {
+    /// The listing of every entry, in order
+    pub fn listings(&self, entries: &[BatchEntry]) -> Result<Vec<ListingParams>, BatchError> {
+        let mut listed = HashSet::new();
+
+        entries
+            .iter()
+            .map(|entry| {
+                let nft_asset_id = match entry.nft {
+                    BatchNft::SubId(sub_id) => {
+                        get_asset_id(sub_id, self.collection.ok_or(BatchError::NoCollection)?)
+                    }
+                    BatchNft::AssetId(asset_id) => asset_id,
+                };
+                if !listed.insert(nft_asset_id) {
+                    return Err(BatchError::Duplicate(nft_asset_id));
+                }
+                let ask_asset = entry.ask_asset.unwrap_or(self.base_asset);
+
+                Ok(ListingParams::new(nft_asset_id, self.receiver)
+                    .with_price(ask_asset, entry.price)
+                    .with_fee(ask_asset, self.fee, self.treasury))
+            })
+            .collect()
+    }
+}
+
+/// How many NFTs one funding transaction sends within the input and output limits of the chain
+///
+/// Every NFT takes a coin input, and a coin and a change output. Some inputs and one change
+/// output are left for the fee
+pub fn listings_per_transaction(max_inputs: u16, max_outputs: u16) -> usize {
+    let by_inputs = usize::from(max_inputs).saturating_sub(FEE_INPUTS);
+    let by_outputs = usize::from(max_outputs).saturating_sub(1) / 2;
+
+    by_inputs.min(by_outputs).max(1)
+}
+
+/// The transaction sending the NFT of every listing to its predicate, not signed yet
+pub async fn funding_transaction_builder(
+    signer: &WalletUnlocked,
+    listings: &[ListingParams],
+) -> Result<ScriptTransactionBuilder, Error> {
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+    for listing in listings {
+        inputs.extend(
+            signer
+                .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+                .await?,
+        );
+        outputs.extend(signer.get_asset_outputs_for_amount(
+            listing.predicate().address(),
+            listing.nft_asset_id,
+            1,
+        ));
+    }
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    signer.adjust_for_fee(&mut tb, 0).await?;
+
+    Ok(tb)
+}
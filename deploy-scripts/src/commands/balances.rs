@@ -0,0 +1,35 @@
+//! `balances`
+
+use crate::{CliResult, Context};
+use clap::Args;
+use fuels::prelude::{Address, Bech32Address};
+
+#[derive(Debug, Args)]
+pub(crate) struct BalancesArgs {
+    /// The account or predicate to inspect, the signer when omitted
+    address: Option<Address>,
+}
+
+pub(crate) async fn balances(context: &Context, args: BalancesArgs) -> CliResult<()> {
+    let address = match args.address {
+        Some(address) => address,
+        None => context.signer()?.address().into(),
+    };
+
+    let mut balances: Vec<_> = context
+        .provider
+        .get_balances(&Bech32Address::from(address))
+        .await?
+        .into_iter()
+        .collect();
+    balances.sort();
+
+    if balances.is_empty() {
+        println!("{address:#x} holds no coins");
+    }
+    for (asset_id, amount) in balances {
+        println!("{asset_id} {amount}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,402 @@
+//! `deploy-collection`, `predict-contract-id`, `mint` and `mint-collection`
+
+use super::{preview, send};
+use crate::{CliResult, Context};
+use clap::Args;
+use deploy_scripts::{
+    manifest::{
+        CollectionDeployment, Manifest, ManifestError, MintRecord, PredicateConfigurables,
+        PredicateDeployment, MANIFEST_VERSION,
+    },
+    metadata::{mint_token, read_tokens, MintProgress, PROGRESS_FILE},
+};
+use fuels::{
+    accounts::Account,
+    prelude::{Address, ContractId, CreateTransactionBuilder, TxPolicies, WalletUnlocked},
+    programs::calls::Execution,
+    tx::StorageSlot,
+    types::{transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity},
+};
+use nullstate_sdk::{
+    collection::{get_asset_id, CollectionConfig, State, NFT},
+    predicate::KNOWN_PREDICATES,
+    serde_hex,
+};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Everything the contract id of a collection depends on besides its bytecode
+#[derive(Debug, Args)]
+pub(crate) struct CollectionConfigArgs {
+    /// Salt of the contract, the zero salt when omitted
+    #[arg(long)]
+    salt: Option<Bytes32>,
+    /// JSON storage slots overriding the ones `forc` generated, as in `*-storage_slots.json`
+    #[arg(long)]
+    storage_slots: Option<PathBuf>,
+}
+
+/// A storage slot as `forc` writes it
+#[derive(Deserialize)]
+struct StorageSlotJson {
+    #[serde(with = "serde_hex")]
+    key: Bytes32,
+    #[serde(with = "serde_hex")]
+    value: Bytes32,
+}
+
+impl CollectionConfigArgs {
+    fn config(&self) -> CliResult<CollectionConfig> {
+        let storage_slots = match &self.storage_slots {
+            Some(path) => {
+                let slots: Vec<StorageSlotJson> = serde_json::from_str(&fs::read_to_string(path)?)
+                    .map_err(|e| format!("invalid storage slots {}: {e}", path.display()))?;
+                slots
+                    .into_iter()
+                    .map(|slot| StorageSlot::new(slot.key, slot.value))
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        Ok(CollectionConfig::new(*self.salt.unwrap_or_default()).with_storage_slots(storage_slots))
+    }
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct DeployCollectionArgs {
+    /// Address allowed to mint and set metadata, the signer when omitted
+    #[arg(long)]
+    owner: Option<Address>,
+    /// Replaces the deployment already recorded in the network's manifest
+    #[arg(long)]
+    force: bool,
+    #[command(flatten)]
+    config: CollectionConfigArgs,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct PredictContractIdArgs {
+    #[command(flatten)]
+    config: CollectionConfigArgs,
+    /// Sub ids to print the asset id of, may be repeated
+    #[arg(long = "sub-id")]
+    sub_ids: Vec<Bytes32>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct MintArgs {
+    /// The collection contract, the one in the network's manifest when omitted
+    #[arg(long)]
+    contract_id: Option<ContractId>,
+    /// Sub id of the token, every token of a collection has its own
+    #[arg(long)]
+    sub_id: Bytes32,
+    /// Address receiving the token, the signer when omitted
+    #[arg(long)]
+    recipient: Option<Address>,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct MintCollectionArgs {
+    /// Directory of JSON metadata files, one per token
+    dir: PathBuf,
+    /// The collection contract, the one in the network's manifest when omitted
+    #[arg(long)]
+    contract_id: Option<ContractId>,
+    /// Address receiving every token, the signer when omitted
+    #[arg(long)]
+    recipient: Option<Address>,
+    /// File recording what was minted, `.mint-progress.json` in the directory when omitted
+    #[arg(long)]
+    progress: Option<PathBuf>,
+}
+
+pub(crate) async fn deploy_collection(
+    context: &Context,
+    args: DeployCollectionArgs,
+) -> CliResult<()> {
+    let signer = context.signer()?;
+    let owner = args.owner.unwrap_or_else(|| signer.address().into());
+    let manifest_path = context.manifest_path();
+
+    // A deployment whose constructor never ran is finished instead of deployed again
+    if !args.force {
+        if let Some(manifest) = Manifest::load(&manifest_path)? {
+            if manifest.collection.constructor_tx.is_none() {
+                manifest.check_chain(context.network.chain_id)?;
+                if args
+                    .owner
+                    .is_some_and(|owner| owner != manifest.collection.owner)
+                {
+                    return Err(format!(
+                        "the deployment at {} is for owner {:#x}, pass --force to deploy again",
+                        manifest_path.display(),
+                        manifest.collection.owner
+                    )
+                    .into());
+                }
+                println!(
+                    "resuming the deployment of {:#x}",
+                    manifest.collection.contract_id
+                );
+
+                return run_constructor(context, signer, manifest, &manifest_path).await;
+            }
+        }
+    }
+    Manifest::ensure_can_deploy(&manifest_path, args.force)?;
+
+    let config = args.config.config()?;
+    let contract = config.contract()?;
+    let contract_id = contract.contract_id();
+    let mut tb = CreateTransactionBuilder::prepare_contract_deployment(
+        contract.code().to_vec(),
+        contract_id,
+        contract.state_root(),
+        contract.salt(),
+        contract.storage_slots().to_vec(),
+        TxPolicies::default(),
+    );
+    signer.adjust_for_fee(&mut tb, 0).await?;
+    let deploy_tx = send(context, &signer, tb).await?;
+    if context.dry_run {
+        // The constructor cannot run against a contract that does not exist yet, only the
+        // deployment itself is previewed
+        println!("the constructor would then set the owner to {owner:#x}");
+
+        return Ok(());
+    }
+    println!("collection deployed at {contract_id:#x}");
+
+    // Saved before the constructor runs so that a failure leaves a deployment to resume
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        network: context.network_name.clone(),
+        chain_id: context.network.chain_id,
+        collection: CollectionDeployment {
+            contract_id,
+            owner,
+            salt: Bytes32::new(*config.salt),
+            deploy_tx,
+            constructor_tx: None,
+        },
+        predicate: PredicateDeployment {
+            bytecode_hash: KNOWN_PREDICATES[0].bytecode_hash(),
+            configurables: PredicateConfigurables::new(
+                context.network.treasury,
+                context.network.fee_policy,
+            ),
+        },
+        mints: vec![],
+    };
+    manifest.save(&manifest_path)?;
+    println!("manifest written to {}", manifest_path.display());
+
+    run_constructor(context, signer, manifest, &manifest_path).await
+}
+
+/// Sets the owner of the collection in `manifest` and records the constructor transaction
+///
+/// A contract whose owner is already set cannot be resumed, it is reported as deployed
+async fn run_constructor(
+    context: &Context,
+    signer: WalletUnlocked,
+    mut manifest: Manifest,
+    manifest_path: &Path,
+) -> CliResult<()> {
+    let owner = manifest.collection.owner;
+    let methods = NFT::new(manifest.collection.contract_id, signer).methods();
+
+    let state = methods
+        .owner()
+        .simulate(Execution::StateReadOnly)
+        .await?
+        .value;
+    if !matches!(state, State::Uninitialized) {
+        return Err(ManifestError::AlreadyDeployed(manifest_path.to_path_buf()).into());
+    }
+
+    let call = methods.constructor(Identity::Address(owner));
+    if context.dry_run {
+        return preview(context, call.build_tx().await?).await;
+    }
+    let response = call.call().await?;
+    println!("owner set to {owner:#x}");
+
+    manifest.collection.constructor_tx = response.tx_id;
+    manifest.save(manifest_path)?;
+    println!("manifest updated at {}", manifest_path.display());
+
+    Ok(())
+}
+
+/// Prints the contract id `deploy-collection` would deploy at, without connecting to a node
+pub(crate) fn predict_contract_id(args: &PredictContractIdArgs) -> CliResult<()> {
+    let contract_id = args.config.config()?.contract_id()?;
+    println!("contract {contract_id:#x}");
+    for sub_id in &args.sub_ids {
+        println!(
+            "sub id {sub_id:#x} asset {:#x}",
+            get_asset_id(*sub_id, contract_id)
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn mint(context: &Context, args: MintArgs) -> CliResult<()> {
+    let signer = context.signer()?;
+    let recipient = args.recipient.unwrap_or_else(|| signer.address().into());
+    let (contract_id, mut manifest) = collection_contract(context, args.contract_id)?;
+
+    let call = NFT::new(contract_id, signer)
+        .methods()
+        .mint(Identity::Address(recipient), Bits256(*args.sub_id), 1)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1));
+    if context.dry_run {
+        return preview(context, call.build_tx().await?).await;
+    }
+    let response = call.call().await?;
+
+    let asset_id = get_asset_id(args.sub_id, contract_id);
+    println!("minted {asset_id:#x} to {recipient:#x}");
+
+    record_mint(
+        context,
+        manifest.as_mut(),
+        contract_id,
+        MintRecord {
+            sub_id: args.sub_id,
+            asset_id,
+            recipient,
+            amount: 1,
+            tx_id: response.tx_id,
+        },
+    )
+}
+
+pub(crate) async fn mint_collection(context: &Context, args: MintCollectionArgs) -> CliResult<()> {
+    let signer = context.signer()?;
+    let recipient = args.recipient.unwrap_or_else(|| signer.address().into());
+    let (contract_id, mut manifest) = collection_contract(context, args.contract_id)?;
+    let tokens = read_tokens(&args.dir)?;
+    let progress_path = args
+        .progress
+        .unwrap_or_else(|| args.dir.join(PROGRESS_FILE));
+    let mut progress = MintProgress::load(&progress_path, contract_id)?;
+    let collection = NFT::new(contract_id, signer);
+
+    if context.dry_run {
+        // Metadata cannot be previewed before its token exists, only the mints are
+        for token in &tokens {
+            if progress
+                .token(token.sub_id)
+                .is_some_and(|token| token.minted)
+            {
+                continue;
+            }
+            let call = collection
+                .methods()
+                .mint(Identity::Address(recipient), Bits256(*token.sub_id), 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1));
+            println!("{}", token.file.display());
+            preview(context, call.build_tx().await?).await?;
+        }
+
+        return Ok(());
+    }
+
+    let (mut minted, mut complete, mut metadata, mut failed) = (0, 0, 0, 0);
+    for token in &tokens {
+        let outcome = match mint_token(
+            &collection,
+            Identity::Address(recipient),
+            token,
+            &mut progress,
+            &progress_path,
+        )
+        .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                eprintln!("{}: {e}", token.file.display());
+                failed += 1;
+                continue;
+            }
+        };
+
+        metadata += outcome.metadata_written;
+        if outcome.minted {
+            minted += 1;
+            record_mint(
+                context,
+                manifest.as_mut(),
+                contract_id,
+                MintRecord {
+                    sub_id: token.sub_id,
+                    asset_id: get_asset_id(token.sub_id, contract_id),
+                    recipient,
+                    amount: 1,
+                    tx_id: progress.token(token.sub_id).and_then(|token| token.mint_tx),
+                },
+            )?;
+        } else if outcome.metadata_written == 0 {
+            complete += 1;
+        }
+    }
+
+    println!(
+        "{} token(s): {minted} minted, {complete} already complete, {metadata} metadata entries written, {failed} failed",
+        tokens.len()
+    );
+    if failed > 0 {
+        return Err(format!(
+            "{failed} token(s) failed, run the command again to resume from {}",
+            progress_path.display()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The collection minted to, `contract_id` or the one in the manifest, with the manifest
+fn collection_contract(
+    context: &Context,
+    contract_id: Option<ContractId>,
+) -> CliResult<(ContractId, Option<Manifest>)> {
+    let manifest_path = context.manifest_path();
+    let manifest = Manifest::load(&manifest_path)?;
+    if let Some(manifest) = &manifest {
+        manifest.check_chain(context.network.chain_id)?;
+    }
+    let contract_id = match (contract_id, &manifest) {
+        (Some(contract_id), _) => contract_id,
+        (None, Some(manifest)) => manifest.collection.contract_id,
+        (None, None) => return Err(ManifestError::NotDeployed(manifest_path).into()),
+    };
+
+    Ok((contract_id, manifest))
+}
+
+/// Records a token of `contract_id` in the manifest
+fn record_mint(
+    context: &Context,
+    manifest: Option<&mut Manifest>,
+    contract_id: ContractId,
+    mint: MintRecord,
+) -> CliResult<()> {
+    // Tokens of other contracts are not part of the deployment
+    if let Some(manifest) =
+        manifest.filter(|manifest| manifest.collection.contract_id == contract_id)
+    {
+        manifest.mints.push(mint);
+        manifest.save(&context.manifest_path())?;
+    }
+
+    Ok(())
+}
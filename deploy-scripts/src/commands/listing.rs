@@ -0,0 +1,217 @@
+//! `list`, `list-batch`, `buy`, `cancel` and `show-listing`, all identifying the listing by its
+//! terms
+
+use super::send;
+use crate::{
+    listing::{FeeArgs, ListingArgs},
+    CliResult, Context,
+};
+use clap::Args;
+use deploy_scripts::{
+    batch::{
+        funding_transaction_builder, listings_per_transaction, read_batch, BatchNft, BatchTerms,
+    },
+    manifest::Manifest,
+};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{Address, ScriptTransactionBuilder, TxPolicies},
+};
+use nullstate_sdk::{
+    order::{Order, OrderMetadata},
+    transactions::{cancel_transaction_builder, FillBuilder},
+};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Args)]
+pub(crate) struct ListArgs {
+    #[command(flatten)]
+    listing: ListingArgs,
+    /// File the order of the funded listing is written to, as JSON
+    #[arg(long, default_value = "order.json")]
+    order: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub(crate) struct ListBatchArgs {
+    /// CSV or JSON file of listings with the columns `sub_id` or `asset_id`, `price`, and
+    /// optionally `ask_asset` and `expiry` (unix seconds)
+    file: PathBuf,
+    /// File the orders of the funded listings are written to, as a JSON array
+    #[arg(long, default_value = "orders.json")]
+    orders: PathBuf,
+    #[command(flatten)]
+    fee: FeeArgs,
+}
+
+pub(crate) async fn list(context: &Context, args: ListArgs) -> CliResult<()> {
+    let signer = context.signer()?;
+    let listing = args
+        .listing
+        .listing(Some(signer.address().into()), &context.network)?;
+    let predicate = listing.predicate();
+
+    // The same transfer `Account::transfer` makes, built here so it can be previewed
+    let inputs = signer
+        .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+        .await?;
+    let outputs = signer.get_asset_outputs_for_amount(predicate.address(), listing.nft_asset_id, 1);
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    signer.adjust_for_fee(&mut tb, 0).await?;
+
+    if send(context, &signer, tb).await?.is_none() {
+        return Ok(());
+    }
+
+    let metadata = OrderMetadata {
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        block_height: context.provider.latest_block_height().await?,
+        expires_at: None,
+    };
+    let order = Order::from_listing(context.network.chain_id, listing, metadata);
+    fs::write(&args.order, order.to_json() + "\n")?;
+    println!(
+        "listed at {:#x}, order written to {}",
+        order.predicate_address,
+        args.order.display()
+    );
+
+    Ok(())
+}
+
+pub(crate) async fn list_batch(context: &Context, args: ListBatchArgs) -> CliResult<()> {
+    let signer = context.signer()?;
+    let entries = read_batch(&args.file)?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    if let Some(row) = entries
+        .iter()
+        .position(|entry| entry.expiry.is_some_and(|expiry| expiry <= now))
+    {
+        return Err(format!("row {} has already expired", row + 1).into());
+    }
+
+    // Sub ids are tokens of the collection deployed on the network
+    let collection = if entries
+        .iter()
+        .any(|entry| matches!(entry.nft, BatchNft::SubId(_)))
+    {
+        let manifest = Manifest::load_deployed(&context.manifest_path(), context.network.chain_id)?;
+        Some(manifest.collection.contract_id)
+    } else {
+        None
+    };
+    let (treasury, fee) = args.fee.terms(&context.network)?;
+    let terms = BatchTerms {
+        receiver: signer.address().into(),
+        treasury,
+        fee,
+        base_asset: context.network.base_asset,
+        collection,
+    };
+    let listings = terms.listings(&entries)?;
+
+    let consensus_parameters = context.provider.consensus_parameters().await?;
+    let tx_params = consensus_parameters.tx_params();
+    let per_transaction = listings_per_transaction(tx_params.max_inputs(), tx_params.max_outputs());
+
+    let mut orders = vec![];
+    let mut transactions = 0;
+    for (listings, entries) in listings
+        .chunks(per_transaction)
+        .zip(entries.chunks(per_transaction))
+    {
+        let tb = funding_transaction_builder(&signer, listings).await?;
+        if send(context, &signer, tb).await?.is_none() {
+            continue;
+        }
+        transactions += 1;
+
+        let metadata = OrderMetadata {
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            block_height: context.provider.latest_block_height().await?,
+            expires_at: None,
+        };
+        orders.extend(listings.iter().zip(entries).map(|(listing, entry)| {
+            Order::from_listing(
+                context.network.chain_id,
+                listing.clone(),
+                OrderMetadata {
+                    expires_at: entry.expiry,
+                    ..metadata
+                },
+            )
+        }));
+        // Written after every transaction so the listings funded before a failure are kept
+        fs::write(&args.orders, serde_json::to_string_pretty(&orders)? + "\n")?;
+    }
+
+    if !context.dry_run {
+        println!(
+            "{} listing(s) funded in {transactions} transaction(s), orders written to {}",
+            orders.len(),
+            args.orders.display()
+        );
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn buy(context: &Context, args: ListingArgs) -> CliResult<()> {
+    let buyer = context.signer()?;
+    let listing = args.listing(None, &context.network)?;
+
+    let tb = FillBuilder::new(&listing, &buyer)
+        .transaction_builder()
+        .await?;
+    send(context, &buyer, tb).await?;
+
+    Ok(())
+}
+
+pub(crate) async fn cancel(context: &Context, args: ListingArgs) -> CliResult<()> {
+    let seller = context.signer()?;
+    let seller_address = Address::from(seller.address());
+    let listing = args.listing(Some(seller_address), &context.network)?;
+    if listing.receiver != seller_address {
+        return Err("only the receiver of a listing can cancel it".into());
+    }
+
+    let tb = cancel_transaction_builder(&listing, &seller).await?;
+    send(context, &seller, tb).await?;
+
+    Ok(())
+}
+
+pub(crate) async fn show_listing(context: &Context, args: ListingArgs) -> CliResult<()> {
+    let signer = match args.receiver {
+        Some(_) => None,
+        None => Some(context.signer()?.address().into()),
+    };
+    let listing = args.listing(signer, &context.network)?;
+    let predicate = listing.predicate().with_provider(context.provider.clone());
+    let held = predicate.get_asset_balance(&listing.nft_asset_id).await?;
+
+    println!("predicate  {:#x}", Address::from(predicate.address()));
+    println!("nft        {:#x}", listing.nft_asset_id);
+    println!("receiver   {:#x}", listing.receiver);
+    println!(
+        "price      {} of {:#x}",
+        listing.ask_amount, listing.ask_asset
+    );
+    println!(
+        "fee        {} of {:#x} to {:#x}",
+        listing.total_fee(),
+        listing.fee_asset,
+        listing.treasury
+    );
+    println!(
+        "status     {}",
+        if held > 0 { "live" } else { "not funded" }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! One module per group of subcommands, each command taking its parsed arguments and the context
+
+pub(crate) mod balances;
+pub(crate) mod collection;
+pub(crate) mod listing;
+pub(crate) mod portfolio;
+
+use crate::{CliResult, Context};
+use deploy_scripts::preview::TxPreview;
+use fuels::{
+    prelude::WalletUnlocked,
+    types::{transaction::Transaction, transaction_builders::TransactionBuilder, Bytes32},
+};
+
+/// Signs the transaction of `tb` and broadcasts it, or only previews it with `--dry-run`
+///
+/// Returns the id of the committed transaction, `None` when it was only previewed
+pub(crate) async fn send<Tb>(
+    context: &Context,
+    signer: &WalletUnlocked,
+    mut tb: Tb,
+) -> CliResult<Option<Bytes32>>
+where
+    Tb: TransactionBuilder,
+    Tb::TxType: Clone,
+{
+    tb.add_signer(signer.clone())?;
+    let tx = tb.build(context.provider.clone()).await?;
+    if context.dry_run {
+        preview(context, tx).await?;
+        return Ok(None);
+    }
+
+    let chain_id = context.provider.consensus_parameters().await?.chain_id();
+    let tx_id = tx.id(chain_id);
+    context
+        .provider
+        .send_transaction_and_await_commit(tx)
+        .await?
+        .check(None)?;
+    println!("transaction {tx_id:#x}");
+
+    Ok(Some(tx_id))
+}
+
+/// Prints what `tx` would do, the dry run failing is reported but is not an error
+pub(crate) async fn preview<T: Transaction + Clone>(context: &Context, tx: T) -> CliResult<()> {
+    println!("{}", TxPreview::dry_run(&context.provider, tx).await?);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! `portfolio`
+
+use crate::{CliResult, Context};
+use clap::Args;
+use deploy_scripts::{manifest::Manifest, portfolio::Portfolio};
+use fuels::prelude::Address;
+use nullstate_indexer::store::{ListingQuery, Store};
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub(crate) struct PortfolioArgs {
+    /// The account to inspect
+    address: Address,
+    /// The indexer's order book the listings and offers are read from
+    #[arg(long, default_value = "orderbook.sqlite")]
+    orderbook: PathBuf,
+    /// Prints JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+pub(crate) async fn portfolio(context: &Context, args: PortfolioArgs) -> CliResult<()> {
+    let manifest = Manifest::load(&context.manifest_path())?;
+    if let Some(manifest) = &manifest {
+        manifest.check_chain(context.network.chain_id)?;
+    }
+
+    // Opening a missing order book would create an empty one
+    let (listings, offers) = if args.orderbook.exists() {
+        let store = Store::open(&args.orderbook)?;
+        let listings = store.listings(&ListingQuery {
+            receiver: Some(args.address),
+            ..ListingQuery::default()
+        })?;
+        (listings, store.offers()?)
+    } else {
+        eprintln!(
+            "no order book at {}, run the indexer to see listings and offers",
+            args.orderbook.display()
+        );
+        (vec![], vec![])
+    };
+
+    let portfolio = Portfolio::fetch(
+        &context.provider,
+        args.address,
+        manifest.as_ref(),
+        &listings,
+        &offers,
+    )
+    .await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&portfolio)?);
+    } else {
+        println!("{portfolio}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+//! The parts of the `nullstate` CLI that are tested on their own
+
+pub mod batch;
+pub mod manifest;
+pub mod metadata;
+pub mod network;
+pub mod portfolio;
+pub mod preview;
+pub mod signer;
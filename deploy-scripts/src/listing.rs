@@ -0,0 +1,86 @@
+//! The terms of a fixed price listing as given on the command line
+
+use crate::CliResult;
+use clap::Args;
+use deploy_scripts::network::NetworkProfile;
+use fuels::prelude::{Address, AssetId};
+use nullstate_sdk::{fee::FeePolicy, listing::ListingParams, predicate::MAX_FEE_BPS};
+
+/// Every term of a listing, the predicate address is derived from all of them
+#[derive(Debug, Args)]
+pub(crate) struct ListingArgs {
+    /// Asset id of the listed NFT
+    #[arg(long)]
+    pub(crate) nft: AssetId,
+    /// Address paid the price, the signer when omitted
+    #[arg(long)]
+    pub(crate) receiver: Option<Address>,
+    /// Amount of the ask asset the receiver is paid
+    #[arg(long)]
+    pub(crate) price: u64,
+    /// Asset the price is paid in, the base asset when omitted
+    #[arg(long)]
+    pub(crate) ask_asset: Option<AssetId>,
+    #[command(flatten)]
+    pub(crate) fee: FeeArgs,
+    /// Asset the fee is paid in, the ask asset when omitted
+    #[arg(long)]
+    pub(crate) fee_asset: Option<AssetId>,
+}
+
+/// Who is paid the marketplace fee and how much
+#[derive(Debug, Args)]
+pub(crate) struct FeeArgs {
+    /// Address paid the marketplace fee, the treasury of the network when omitted
+    #[arg(long)]
+    pub(crate) treasury: Option<Address>,
+    /// Fixed fee, the fee policy of the network applies when neither fee is given
+    #[arg(long, conflicts_with = "fee_bps")]
+    pub(crate) fee: Option<u64>,
+    /// Fee in basis points of the price
+    #[arg(long)]
+    pub(crate) fee_bps: Option<u64>,
+}
+
+impl FeeArgs {
+    /// The treasury and the fee policy, the ones of `network` when not given
+    pub(crate) fn terms(&self, network: &NetworkProfile) -> CliResult<(Address, FeePolicy)> {
+        let fee = match (self.fee, self.fee_bps) {
+            (Some(fee), _) => FeePolicy::Fixed(fee),
+            (None, Some(bps)) => FeePolicy::Bps(bps),
+            (None, None) => network.fee_policy,
+        };
+        if let FeePolicy::Bps(bps) = fee {
+            if bps > MAX_FEE_BPS {
+                return Err(format!("fee of {bps} bps is above the whole price").into());
+            }
+        }
+
+        let treasury = self
+            .treasury
+            .or(network.treasury)
+            .ok_or("--treasury is required, the network has no treasury configured")?;
+
+        Ok((treasury, fee))
+    }
+}
+
+impl ListingArgs {
+    /// Resolves the defaults from `network`, `signer` is the receiver when none is given
+    pub(crate) fn listing(
+        &self,
+        signer: Option<Address>,
+        network: &NetworkProfile,
+    ) -> CliResult<ListingParams> {
+        let (treasury, fee) = self.fee.terms(network)?;
+        let receiver = self
+            .receiver
+            .or(signer)
+            .ok_or("--receiver is required without a signer")?;
+        let ask_asset = self.ask_asset.unwrap_or(network.base_asset);
+
+        Ok(ListingParams::new(self.nft, receiver)
+            .with_price(ask_asset, self.price)
+            .with_fee(self.fee_asset.unwrap_or(ask_asset), fee, treasury))
+    }
+}
@@ -1,192 +1,143 @@
-use fuels::{
-    crypto::SecretKey,
-    prelude::*,
-    types::{output::Output, Bits256, Bytes32},
+mod commands;
+mod listing;
+
+use clap::{Parser, Subcommand};
+use commands::{balances, collection, listing as listing_commands, portfolio};
+use deploy_scripts::{
+    manifest::manifest_path,
+    network::{NetworkConfig, NetworkProfile},
+    signer::{SignerArgs, SignerSource},
 };
-use sha2::{Digest, Sha256};
+use fuels::prelude::{Address, Provider, WalletUnlocked};
+use listing::ListingArgs;
+use std::path::PathBuf;
+
+pub(crate) type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "nullstate",
+    about = "Operates the nullstate NFT marketplace on a Fuel network"
+)]
+struct Cli {
+    /// The network profile to use, `local` runs fuel-core in-process
+    #[arg(long, global = true, default_value = "testnet")]
+    network: String,
+    /// TOML file of network profiles, the bundled `networks.toml` when omitted
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Directory holding one deployment manifest per network
+    #[arg(long, global = true, default_value = "deployments")]
+    deployments: PathBuf,
+    /// Runs the transactions of the command on the node and prints them without broadcasting
+    #[arg(long, global = true)]
+    dry_run: bool,
+    #[command(flatten)]
+    signer: SignerArgs,
+    #[command(subcommand)]
+    command: Command,
+}
 
-abigen!(
-    Contract(
-        name = "NFT",
-        abi = "../NFT-contract/out/debug/NFT-contract-abi.json"
-    ),
-    Predicate(
-        name = "MyPredicate",
-        abi = "../nft_fixed_price_swap_predicate/out/debug/nft_fixed_price_swap_predicate-abi.json"
-    )
-);
+#[derive(Subcommand)]
+enum Command {
+    /// Deploys a new NFT collection contract and records it in the network's manifest
+    DeployCollection(collection::DeployCollectionArgs),
+    /// Prints the contract id a collection deployed with a salt and storage would get
+    PredictContractId(collection::PredictContractIdArgs),
+    /// Mints a token of a collection
+    Mint(collection::MintArgs),
+    /// Mints a token for every metadata file of a directory and writes its metadata
+    MintCollection(collection::MintCollectionArgs),
+    /// Lists an NFT at a fixed price by sending it to the listing predicate and writes its order
+    List(listing_commands::ListArgs),
+    /// Lists every NFT of a CSV or JSON file and writes their orders
+    ListBatch(listing_commands::ListBatchArgs),
+    /// Buys a listed NFT, paying the price and the fee
+    Buy(ListingArgs),
+    /// Takes a listed NFT back, only the receiver of the listing can cancel it
+    Cancel(ListingArgs),
+    /// Prints the predicate address of a listing and whether it holds the NFT
+    ShowListing(ListingArgs),
+    /// Prints the balance of every asset held by an address
+    Balances(balances::BalancesArgs),
+    /// Prints the NFTs an address holds, the listings paying it, its open offers and its ask
+    /// asset balances
+    ///
+    /// Tokens held exactly once count as NFTs, those minted through the collection of the
+    /// network's manifest also show their collection and sub id
+    Portfolio(portfolio::PortfolioArgs),
+}
 
-const PREDICATE_BINARY: &str =
-    "../nft_fixed_price_swap_predicate/out/debug/nft_fixed_price_swap_predicate.bin";
+/// What every command runs against
+pub(crate) struct Context {
+    pub(crate) provider: Provider,
+    pub(crate) network_name: String,
+    pub(crate) network: NetworkProfile,
+    deployments: PathBuf,
+    /// Transactions are previewed instead of sent
+    pub(crate) dry_run: bool,
+    signer: Option<WalletUnlocked>,
+}
 
-pub const ASSET_ID: AssetId = AssetId::new([
-    0xf8, 0xf8, 0xb6, 0x28, 0x3d, 0x7f, 0xa5, 0xb6, 0x72, 0xb5, 0x30, 0xcb, 0xb8, 0x4f, 0xcc, 0xcb,
-    0x4f, 0xf8, 0xdc, 0x40, 0xf8, 0x17, 0x6e, 0xf4, 0x54, 0x4d, 0xdb, 0x1f, 0x19, 0x52, 0xad, 0x07,
-]);
+impl Context {
+    /// The wallet signing transactions, commands that only read the chain never ask for it
+    pub(crate) fn signer(&self) -> CliResult<WalletUnlocked> {
+        Ok(self.signer.clone().ok_or(
+            "a signer is required, pass --keystore or --mnemonic or set NULLSTATE_SECRET_KEY",
+        )?)
+    }
+
+    /// The deployment manifest of the selected network
+    pub(crate) fn manifest_path(&self) -> PathBuf {
+        manifest_path(&self.deployments, &self.network_name)
+    }
+}
 
 #[tokio::main]
-pub async fn main() {
-    // Create a provider pointing to the testnet.
-    let provider = Provider::connect("testnet.fuel.network").await.unwrap();
-
-    let secret_key_seller: SecretKey =
-        "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301"
-            .parse()
-            .unwrap();
-
-    let secret_key_buyer: SecretKey =
-        "0x37fa81c84ccd547c30c176b118d5cb892bdb113e8e80141f266519422ef9eefd"
-            .parse()
-            .unwrap();
-
-    let secret_key_treasuery: SecretKey =
-        "0x976e5c3fa620092c718d852ca703b6da9e3075b9f2ecb8ed42d9f746bf26aafb"
-            .parse()
-            .unwrap();
-
-    let mut wallet_seller =
-        WalletUnlocked::new_from_private_key(secret_key_seller, Some(provider.clone()));
-    let mut wallet_buyer =
-        WalletUnlocked::new_from_private_key(secret_key_buyer, Some(provider.clone()));
-    let mut wallet_treasuery =
-        WalletUnlocked::new_from_private_key(secret_key_treasuery, Some(provider.clone()));
-
-    let mut nft_contract_owner = WalletUnlocked::new_random(Some(provider.clone()));
-    dbg!(wallet_buyer.get_asset_balance(&ASSET_ID).await.unwrap());
-
-    let contract_id = Contract::load_from(
-        "../NFT-contract/out/debug/NFT-contract.bin",
-        LoadConfiguration::default(),
-    )
-    .unwrap()
-    .deploy(
-        &wallet_buyer,
-        TxPolicies::new(Some(1), None, None, Some(1_000_000), None),
-    )
-    .await
-    .unwrap();
-
-    let nft_instance = NFT::new(contract_id.clone(), wallet_buyer.clone());
-    let sub_id_1 = Bytes32::from([1u8; 32]);
-
-    nft_instance
-        .methods()
-        .constructor(wallet_buyer.address().into())
-        .call()
-        .await
-        .unwrap();
-
-    nft_instance
-        .methods()
-        .mint(wallet_seller.address().into(), Bits256(*sub_id_1), 1)
-        .append_variable_outputs(1)
-        .call()
-        .await
-        .unwrap();
-
-    let nft_asset_id = get_asset_id(sub_id_1, contract_id.into());
-    let predicate = Predicate::load_from(PREDICATE_BINARY)
-        .unwrap()
-        .with_configurables(
-            MyPredicateConfigurables::default()
-                .with_ASK_AMOUNT(40)
-                .unwrap()
-                .with_ASK_ASSET(ASSET_ID)
-                .unwrap()
-                .with_FEE_AMOUNT(2)
-                .unwrap()
-                .with_TREASURY_ADDRESS(wallet_treasuery.address().into())
-                .unwrap()
-                .with_FEE_ASSET(ASSET_ID)
-                .unwrap()
-                .with_NFT_ASSET_ID(nft_asset_id)
-                .unwrap()
-                .with_RECEIVER(wallet_seller.address().into())
-                .unwrap(),
-        )
-        .with_provider(provider.clone());
-
-    let offered_amount = 1;
-    wallet_seller
-        .transfer(
-            predicate.address(),
-            offered_amount,
-            nft_asset_id,
-            TxPolicies::default(),
-        )
-        .await
-        .unwrap();
-
-    assert_eq!(
-        predicate.get_asset_balance(&nft_asset_id).await.unwrap(),
-        offered_amount
-    );
-    dbg!(predicate.get_asset_balance(&nft_asset_id).await.unwrap());
-
-    let input_predicate = predicate
-        .get_asset_inputs_for_amount(nft_asset_id, 1)
-        .await
-        .unwrap()[0]
-        .clone();
-
-    // Get input from taker
-    let input_from_taker = wallet_buyer
-        .get_asset_inputs_for_amount(ASSET_ID, 1)
-        .await
-        .unwrap()[0]
+pub async fn main() -> CliResult<()> {
+    let cli = Cli::parse();
+    // Predicting a contract id needs neither a node nor a signer
+    if let Command::PredictContractId(args) = &cli.command {
+        return collection::predict_contract_id(args);
+    }
+
+    let network = NetworkConfig::load(cli.config.as_deref())?
+        .profile(&cli.network)?
         .clone();
 
-    // Output for the asked coin transferred from the taker to the receiver
-    let output_to_receiver = Output::Coin {
-        to: Address::from(wallet_seller.address().clone()),
-        amount: 40,
-        asset_id: ASSET_ID,
-    };
-
-    let output_to_treasury = Output::Coin {
-        to: Address::from(wallet_treasuery.address().clone()),
-        amount: 2,
-        asset_id: ASSET_ID,
-    };
-
-    // Output for the offered coin transferred from the predicate to the order taker
-    let output_to_taker = Output::Coin {
-        to: Address::from(wallet_buyer.address()),
-        amount: offered_amount,
-        asset_id: nft_asset_id,
-    };
-
-    // Change output for unspent asked asset
-    let output_asked_change = Output::Change {
-        to: Address::from(wallet_buyer.address()),
-        amount: 0,
-        asset_id: nft_asset_id,
+    // The signer is unlocked before connecting, an in-process node funds it
+    let mut signer = SignerSource::from_env(&cli.signer)?
+        .map(|source| source.load(None))
+        .transpose()?;
+    let funded: Vec<Address> = signer
+        .iter()
+        .map(|wallet| wallet.address().into())
+        .collect();
+    let provider = network.connect(&funded).await?;
+    if let Some(wallet) = &mut signer {
+        wallet.set_provider(provider.clone());
+    }
+
+    let context = Context {
+        provider,
+        network_name: cli.network,
+        network,
+        deployments: cli.deployments,
+        dry_run: cli.dry_run,
+        signer,
     };
 
-    let mut tb = ScriptTransactionBuilder::prepare_transfer(
-        vec![input_predicate, input_from_taker],
-        vec![
-            output_to_receiver,
-            output_to_treasury,
-            output_to_taker,
-            output_asked_change,
-        ],
-        TxPolicies::default(),
-    );
-    tb.add_signer(wallet_buyer.clone()).unwrap();
-    let tx = tb.build(provider.clone()).await.unwrap();
-
-    let _tx_status = provider
-        .send_transaction_and_await_commit(tx)
-        .await
-        .unwrap();
-
-    println!("{:?}", _tx_status);
-}
-
-pub(crate) fn get_asset_id(sub_id: Bytes32, contract: ContractId) -> AssetId {
-    let mut hasher = Sha256::new();
-    hasher.update(*contract);
-    hasher.update(*sub_id);
-    AssetId::new(*Bytes32::from(<[u8; 32]>::from(hasher.finalize())))
+    match cli.command {
+        Command::DeployCollection(args) => collection::deploy_collection(&context, args).await,
+        Command::PredictContractId(_) => unreachable!("handled before connecting"),
+        Command::Mint(args) => collection::mint(&context, args).await,
+        Command::MintCollection(args) => collection::mint_collection(&context, args).await,
+        Command::List(args) => listing_commands::list(&context, args).await,
+        Command::ListBatch(args) => listing_commands::list_batch(&context, args).await,
+        Command::Buy(args) => listing_commands::buy(&context, args).await,
+        Command::Cancel(args) => listing_commands::cancel(&context, args).await,
+        Command::ShowListing(args) => listing_commands::show_listing(&context, args).await,
+        Command::Balances(args) => balances::balances(&context, args).await,
+        Command::Portfolio(args) => portfolio::portfolio(&context, args).await,
+    }
 }
@@ -0,0 +1,203 @@
+//! The deployment manifest of a network, written by `deploy-collection`
+//!
+//! One JSON file per network records what was deployed there, so later commands read the
+//! collection contract from it instead of taking its id on the command line
+
+use fuels::{
+    prelude::{Address, AssetId, ContractId},
+    types::Bytes32,
+};
+use nullstate_sdk::{fee::FeePolicy, serde_hex};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The manifest format written by this version of the CLI
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// The collection contract and how it was set up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionDeployment {
+    #[serde(with = "serde_hex")]
+    pub contract_id: ContractId,
+    /// Allowed to mint and set metadata
+    #[serde(with = "serde_hex")]
+    pub owner: Address,
+    /// The salt the contract was deployed with, zero for manifests written before salts
+    #[serde(default, with = "serde_hex")]
+    pub salt: Bytes32,
+    /// The transaction creating the contract, `None` in manifests written before it was recorded
+    #[serde(with = "serde_hex::option")]
+    pub deploy_tx: Option<Bytes32>,
+    /// The transaction setting the owner, `None` until the constructor succeeded
+    #[serde(with = "serde_hex::option")]
+    pub constructor_tx: Option<Bytes32>,
+}
+
+/// The configurables the CLI sets on every listing predicate of the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredicateConfigurables {
+    #[serde(rename = "TREASURY_ADDRESS", with = "serde_hex::option")]
+    pub treasury: Option<Address>,
+    #[serde(rename = "FEE_AMOUNT")]
+    pub fee_amount: u64,
+    #[serde(rename = "FEE_BPS")]
+    pub fee_bps: u64,
+}
+
+impl PredicateConfigurables {
+    pub fn new(treasury: Option<Address>, fee_policy: FeePolicy) -> Self {
+        let (fee_amount, fee_bps) = fee_policy.configurables();
+
+        Self {
+            treasury,
+            fee_amount,
+            fee_bps,
+        }
+    }
+}
+
+/// The listing predicate release listings of the network are made with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredicateDeployment {
+    /// sha256 of the predicate bytecode before configurables are set
+    #[serde(with = "serde_hex")]
+    pub bytecode_hash: Bytes32,
+    pub configurables: PredicateConfigurables,
+}
+
+/// A token minted through the CLI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintRecord {
+    #[serde(with = "serde_hex")]
+    pub sub_id: Bytes32,
+    #[serde(with = "serde_hex")]
+    pub asset_id: AssetId,
+    #[serde(with = "serde_hex")]
+    pub recipient: Address,
+    pub amount: u64,
+    #[serde(with = "serde_hex::option")]
+    pub tx_id: Option<Bytes32>,
+}
+
+/// Everything deployed on one network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub network: String,
+    pub chain_id: u64,
+    pub collection: CollectionDeployment,
+    pub predicate: PredicateDeployment,
+    #[serde(default)]
+    pub mints: Vec<MintRecord>,
+}
+
+/// Why a manifest could not be read or written
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(PathBuf, std::io::Error),
+    Json(PathBuf, serde_json::Error),
+    /// The manifest was written by a newer or unknown version of the CLI
+    UnsupportedVersion(u32),
+    /// A deployment is already recorded, overwriting it needs `--force`
+    AlreadyDeployed(PathBuf),
+    /// Nothing was deployed on the network yet
+    NotDeployed(PathBuf),
+    /// The manifest was written for another chain than the network runs
+    WrongChain {
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "cannot access {}: {e}", path.display()),
+            Self::Json(path, e) => write!(f, "invalid manifest {}: {e}", path.display()),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported manifest version {version}")
+            }
+            Self::AlreadyDeployed(path) => write!(
+                f,
+                "{} already records a deployment, pass --force to replace it",
+                path.display()
+            ),
+            Self::NotDeployed(path) => write!(
+                f,
+                "no deployment manifest at {}, run deploy-collection first",
+                path.display()
+            ),
+            Self::WrongChain { expected, found } => write!(
+                f,
+                "manifest is for chain {found} but the network runs chain {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Where the manifest of `network` lives within `dir`
+pub fn manifest_path(dir: &Path, network: &str) -> PathBuf {
+    dir.join(format!("{network}.json"))
+}
+
+impl Manifest {
+    /// Reads the manifest at `path`, `None` when the file does not exist
+    pub fn load(path: &Path) -> Result<Option<Self>, ManifestError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ManifestError::Io(path.to_path_buf(), e)),
+        };
+
+        let manifest: Self =
+            serde_json::from_str(&json).map_err(|e| ManifestError::Json(path.to_path_buf(), e))?;
+        if manifest.version != MANIFEST_VERSION {
+            return Err(ManifestError::UnsupportedVersion(manifest.version));
+        }
+
+        Ok(Some(manifest))
+    }
+
+    /// Reads the manifest at `path` and checks it was written for `chain_id`
+    pub fn load_deployed(path: &Path, chain_id: u64) -> Result<Self, ManifestError> {
+        let manifest =
+            Self::load(path)?.ok_or_else(|| ManifestError::NotDeployed(path.to_path_buf()))?;
+        manifest.check_chain(chain_id)?;
+
+        Ok(manifest)
+    }
+
+    /// Fails when the manifest was written for another chain than `chain_id`
+    pub fn check_chain(&self, chain_id: u64) -> Result<(), ManifestError> {
+        if self.chain_id != chain_id {
+            return Err(ManifestError::WrongChain {
+                expected: chain_id,
+                found: self.chain_id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Fails when a deployment is recorded at `path`, unless `force` is set
+    pub fn ensure_can_deploy(path: &Path, force: bool) -> Result<(), ManifestError> {
+        if !force && path.exists() {
+            return Err(ManifestError::AlreadyDeployed(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Writes the manifest to `path`, creating its directory when missing
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| ManifestError::Io(dir.to_path_buf(), e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self).expect("manifests always serialize");
+        fs::write(path, json + "\n").map_err(|e| ManifestError::Io(path.to_path_buf(), e))
+    }
+}
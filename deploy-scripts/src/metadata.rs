@@ -0,0 +1,317 @@
+//! The token metadata files of `mint-collection` and the progress of minting them
+//!
+//! A drop is a directory holding one JSON object per token. Every key of the object is written
+//! as an SRC-7 metadata entry of the token, `name` and `symbol` included, except `sub_id` which
+//! picks the token. Without it the file name must be a number, used as the sub id
+//!
+//! Every mint and every metadata entry is its own transaction. Each one is recorded in a
+//! progress file as soon as it is committed, so minting the directory again resumes where a
+//! failure stopped it. The contract is read before sending anything the progress file does not
+//! record, so a transaction committed without being recorded is not sent again
+
+use fuels::{
+    prelude::{ContractId, WalletUnlocked},
+    programs::calls::Execution,
+    types::{
+        errors::Error, transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity,
+    },
+};
+use nullstate_sdk::{
+    collection::{get_asset_id, Metadata, NFT},
+    serde_hex,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The key of a metadata file naming the sub id instead of the file name
+pub const SUB_ID_KEY: &str = "sub_id";
+
+/// The progress file kept in the drop directory when no other is given
+pub const PROGRESS_FILE: &str = ".mint-progress.json";
+
+/// One token of a drop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The file the token was read from
+    pub file: PathBuf,
+    pub sub_id: Bytes32,
+    /// SRC-7 entries in key order, strings and integers are kept as such and any other JSON
+    /// value is written as its JSON text
+    pub metadata: Vec<(String, Metadata)>,
+}
+
+/// What minting a token sent, nothing when it was already done
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenOutcome {
+    pub minted: bool,
+    pub metadata_written: usize,
+}
+
+/// How far minting a token got
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenProgress {
+    #[serde(with = "serde_hex")]
+    pub sub_id: Bytes32,
+    pub minted: bool,
+    /// `None` when the token was found minted instead of being minted here
+    #[serde(with = "serde_hex::option")]
+    pub mint_tx: Option<Bytes32>,
+    /// The metadata keys already written
+    pub metadata: Vec<String>,
+}
+
+/// How far minting a drop got, saved after every transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MintProgress {
+    #[serde(with = "serde_hex")]
+    pub contract_id: ContractId,
+    pub tokens: Vec<TokenProgress>,
+}
+
+/// Why a drop could not be read or minted
+#[derive(Debug)]
+pub enum MintError {
+    Io(PathBuf, std::io::Error),
+    Json(PathBuf, serde_json::Error),
+    /// The file has no valid `sub_id` and its name is not a number
+    InvalidSubId(PathBuf),
+    /// Two files are the same token
+    DuplicateSubId(Bytes32),
+    /// The directory holds no metadata file
+    Empty(PathBuf),
+    /// The progress file was written while minting to another contract
+    OtherContract {
+        expected: ContractId,
+        found: ContractId,
+    },
+    /// A mint or metadata transaction failed
+    Transaction(Error),
+}
+
+impl fmt::Display for MintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "cannot access {}: {e}", path.display()),
+            Self::Json(path, e) => write!(f, "invalid JSON in {}: {e}", path.display()),
+            Self::InvalidSubId(path) => write!(
+                f,
+                "{} needs a `{SUB_ID_KEY}` of 32 hex bytes or a number as its name",
+                path.display()
+            ),
+            Self::DuplicateSubId(sub_id) => write!(f, "sub id {sub_id:#x} is minted twice"),
+            Self::Empty(dir) => write!(f, "no metadata file in {}", dir.display()),
+            Self::OtherContract { expected, found } => write!(
+                f,
+                "progress is for contract {found:#x}, not {expected:#x}, remove it to start over"
+            ),
+            Self::Transaction(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for MintError {}
+
+impl From<Error> for MintError {
+    fn from(e: Error) -> Self {
+        Self::Transaction(e)
+    }
+}
+
+/// Reads every `.json` file of `dir`, hidden files excepted, ordered by sub id
+pub fn read_tokens(dir: &Path) -> Result<Vec<Token>, MintError> {
+    let io_error = |e| MintError::Io(dir.to_path_buf(), e);
+    let mut tokens = vec![];
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if name.starts_with('.') || !name.ends_with(".json") {
+            continue;
+        }
+
+        let json = fs::read_to_string(&path).map_err(|e| MintError::Io(path.clone(), e))?;
+        tokens.push(parse_token(&path, &json)?);
+    }
+
+    tokens.sort_by_key(|token| token.sub_id);
+    if let Some(pair) = tokens
+        .windows(2)
+        .find(|pair| pair[0].sub_id == pair[1].sub_id)
+    {
+        return Err(MintError::DuplicateSubId(pair[0].sub_id));
+    }
+    if tokens.is_empty() {
+        return Err(MintError::Empty(dir.to_path_buf()));
+    }
+
+    Ok(tokens)
+}
+
+/// Reads the metadata file at `path` holding `json`
+pub fn parse_token(path: &Path, json: &str) -> Result<Token, MintError> {
+    let fields: serde_json::Map<String, Value> =
+        serde_json::from_str(json).map_err(|e| MintError::Json(path.to_path_buf(), e))?;
+    let invalid_sub_id = || MintError::InvalidSubId(path.to_path_buf());
+
+    let mut sub_id = None;
+    let mut metadata = vec![];
+    for (key, value) in fields {
+        if key == SUB_ID_KEY {
+            let hex = value.as_str().ok_or_else(invalid_sub_id)?;
+            sub_id = Some(hex.parse::<Bytes32>().map_err(|_| invalid_sub_id())?);
+            continue;
+        }
+
+        let value = match value {
+            Value::Null => continue,
+            Value::String(string) => Metadata::String(string),
+            Value::Number(number) => match number.as_u64() {
+                Some(int) => Metadata::Int(int),
+                None => Metadata::String(number.to_string()),
+            },
+            value => Metadata::String(value.to_string()),
+        };
+        metadata.push((key, value));
+    }
+
+    // `7.json` is the token of sub id 7, as a big endian 32 byte number
+    let sub_id = match sub_id {
+        Some(sub_id) => sub_id,
+        None => {
+            let number: u64 = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok())
+                .ok_or_else(invalid_sub_id)?;
+            let mut bytes = [0u8; 32];
+            bytes[24..].copy_from_slice(&number.to_be_bytes());
+            Bytes32::new(bytes)
+        }
+    };
+
+    Ok(Token {
+        file: path.to_path_buf(),
+        sub_id,
+        metadata,
+    })
+}
+
+impl MintProgress {
+    /// Reads the progress at `path` of minting to `contract_id`, none yet when it does not exist
+    pub fn load(path: &Path, contract_id: ContractId) -> Result<Self, MintError> {
+        let json = match fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    contract_id,
+                    tokens: vec![],
+                })
+            }
+            Err(e) => return Err(MintError::Io(path.to_path_buf(), e)),
+        };
+
+        let progress: Self =
+            serde_json::from_str(&json).map_err(|e| MintError::Json(path.to_path_buf(), e))?;
+        if progress.contract_id != contract_id {
+            return Err(MintError::OtherContract {
+                expected: contract_id,
+                found: progress.contract_id,
+            });
+        }
+
+        Ok(progress)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MintError> {
+        let json = serde_json::to_string_pretty(self).expect("progress always serializes");
+        fs::write(path, json + "\n").map_err(|e| MintError::Io(path.to_path_buf(), e))
+    }
+
+    /// The progress of `sub_id`, nothing done yet when it is not recorded
+    pub fn token(&self, sub_id: Bytes32) -> Option<&TokenProgress> {
+        self.tokens.iter().find(|token| token.sub_id == sub_id)
+    }
+
+    fn token_index(&mut self, sub_id: Bytes32) -> usize {
+        match self.tokens.iter().position(|token| token.sub_id == sub_id) {
+            Some(index) => index,
+            None => {
+                self.tokens.push(TokenProgress {
+                    sub_id,
+                    minted: false,
+                    mint_tx: None,
+                    metadata: vec![],
+                });
+                self.tokens.len() - 1
+            }
+        }
+    }
+}
+
+/// Mints `token` to `recipient` and writes its metadata, skipping what `progress` records or
+/// the contract already holds
+///
+/// `progress` is saved to `progress_path` after every step, the collection must be called by
+/// its owner
+pub async fn mint_token(
+    collection: &NFT<WalletUnlocked>,
+    recipient: Identity,
+    token: &Token,
+    progress: &mut MintProgress,
+    progress_path: &Path,
+) -> Result<TokenOutcome, MintError> {
+    let asset_id = get_asset_id(token.sub_id, progress.contract_id);
+    let index = progress.token_index(token.sub_id);
+    let mut outcome = TokenOutcome::default();
+
+    if !progress.tokens[index].minted {
+        let supply = collection
+            .methods()
+            .total_supply(asset_id)
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value;
+        if supply.is_none() {
+            let response = collection
+                .methods()
+                .mint(recipient, Bits256(*token.sub_id), 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await?;
+            progress.tokens[index].mint_tx = response.tx_id;
+            outcome.minted = true;
+        }
+        progress.tokens[index].minted = true;
+        progress.save(progress_path)?;
+    }
+
+    for (key, value) in &token.metadata {
+        if progress.tokens[index].metadata.contains(key) {
+            continue;
+        }
+        let stored = collection
+            .methods()
+            .metadata(asset_id, key.clone())
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value;
+        if stored.is_none() {
+            collection
+                .methods()
+                .set_metadata(asset_id, key.clone(), value.clone())
+                .call()
+                .await?;
+            outcome.metadata_written += 1;
+        }
+        progress.tokens[index].metadata.push(key.clone());
+        progress.save(progress_path)?;
+    }
+
+    Ok(outcome)
+}
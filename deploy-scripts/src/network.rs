@@ -0,0 +1,227 @@
+//! Named network profiles the CLI connects to
+//!
+//! Profiles are read from a TOML file, one table per network. A profile without a node URL
+//! starts fuel-core in-process, with the chain id and base asset of the profile, for as long as
+//! the command runs. Every command gets a fresh chain, so nothing deployed, minted or listed by
+//! one command is seen by the next
+
+use fuels::{
+    prelude::{Address, AssetId, Provider},
+    test_helpers::{setup_single_asset_coins, setup_test_provider, ChainConfig, NodeConfig},
+    types::errors::Error,
+};
+use nullstate_sdk::{fee::FeePolicy, predicate::MAX_FEE_BPS, serde_hex};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The profiles used when no config file is given
+pub const DEFAULT_NETWORKS: &str = include_str!("../networks.toml");
+
+/// Coins of the base asset an in-process node gives each funded address
+const LOCAL_COINS: u64 = 10;
+const LOCAL_COIN_AMOUNT: u64 = 1_000_000_000;
+
+/// Everything the CLI needs to know about a network
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkProfile {
+    /// The node to connect to, fuel-core is started in-process when omitted
+    pub node_url: Option<String>,
+    pub chain_id: u64,
+    #[serde(with = "serde_hex")]
+    pub base_asset: AssetId,
+    /// Paid the fee of listings that do not name a treasury
+    #[serde(default, with = "serde_hex::option")]
+    pub treasury: Option<Address>,
+    /// How the marketplace fee of new listings is computed, no fee when omitted
+    #[serde(default)]
+    pub fee_policy: FeePolicy,
+}
+
+/// Every profile of a config file, by name
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct NetworkConfig {
+    pub networks: BTreeMap<String, NetworkProfile>,
+}
+
+/// Why no provider could be set up for a network
+#[derive(Debug)]
+pub enum NetworkError {
+    Io(PathBuf, std::io::Error),
+    Toml(toml::de::Error),
+    /// The fee policy of a profile takes more than the whole price
+    FeeAboveWholePrice {
+        name: String,
+        bps: u64,
+    },
+    /// The config has no profile of this name
+    UnknownNetwork {
+        name: String,
+        known: Vec<String>,
+    },
+    /// The node runs another chain than the profile describes
+    ChainIdMismatch {
+        expected: u64,
+        found: u64,
+    },
+    /// The node uses another base asset than the profile describes
+    BaseAssetMismatch {
+        expected: AssetId,
+        found: AssetId,
+    },
+    Provider(Error),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "cannot read {}: {e}", path.display()),
+            Self::Toml(e) => write!(f, "invalid network config: {e}"),
+            Self::FeeAboveWholePrice { name, bps } => write!(
+                f,
+                "network `{name}` charges a fee of {bps} bps, above the whole price"
+            ),
+            Self::UnknownNetwork { name, known } => write!(
+                f,
+                "unknown network `{name}`, expected one of {}",
+                known.join(", ")
+            ),
+            Self::ChainIdMismatch { expected, found } => {
+                write!(
+                    f,
+                    "node runs chain {found}, the profile expects chain {expected}"
+                )
+            }
+            Self::BaseAssetMismatch { expected, found } => write!(
+                f,
+                "node uses base asset {found:#x}, the profile expects {expected:#x}"
+            ),
+            Self::Provider(e) => write!(f, "cannot reach the node: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<Error> for NetworkError {
+    fn from(e: Error) -> Self {
+        Self::Provider(e)
+    }
+}
+
+impl FromStr for NetworkConfig {
+    type Err = NetworkError;
+
+    fn from_str(toml: &str) -> Result<Self, Self::Err> {
+        let config: Self = toml::from_str(toml).map_err(NetworkError::Toml)?;
+
+        // Checked as `--fee-bps` is, a profile must not create listings no one can fill
+        for (name, profile) in &config.networks {
+            if let FeePolicy::Bps(bps) = profile.fee_policy {
+                if bps > MAX_FEE_BPS {
+                    return Err(NetworkError::FeeAboveWholePrice {
+                        name: name.clone(),
+                        bps,
+                    });
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        DEFAULT_NETWORKS
+            .parse()
+            .expect("the bundled network config is valid")
+    }
+}
+
+impl NetworkConfig {
+    /// Reads the profiles at `path`, or the bundled ones when no path is given
+    pub fn load(path: Option<&Path>) -> Result<Self, NetworkError> {
+        match path {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| NetworkError::Io(path.to_path_buf(), e))?
+                .parse(),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&NetworkProfile, NetworkError> {
+        self.networks
+            .get(name)
+            .ok_or_else(|| NetworkError::UnknownNetwork {
+                name: name.to_string(),
+                known: self.networks.keys().cloned().collect(),
+            })
+    }
+}
+
+impl NetworkProfile {
+    /// Connects to the node of the profile and checks it runs the expected chain
+    ///
+    /// An in-process node gives every address in `funded` coins of the base asset to pay with
+    pub async fn connect(&self, funded: &[Address]) -> Result<Provider, NetworkError> {
+        let provider = match &self.node_url {
+            Some(node_url) => Provider::connect(node_url).await?,
+            None => self.launch_local_node(funded).await?,
+        };
+
+        let consensus_parameters = provider.consensus_parameters().await?;
+        let found = u64::from(consensus_parameters.chain_id());
+        if found != self.chain_id {
+            return Err(NetworkError::ChainIdMismatch {
+                expected: self.chain_id,
+                found,
+            });
+        }
+        let found = *consensus_parameters.base_asset_id();
+        if found != self.base_asset {
+            return Err(NetworkError::BaseAssetMismatch {
+                expected: self.base_asset,
+                found,
+            });
+        }
+
+        Ok(provider)
+    }
+
+    async fn launch_local_node(&self, funded: &[Address]) -> Result<Provider, Error> {
+        let coins = funded
+            .iter()
+            .flat_map(|address| {
+                setup_single_asset_coins(
+                    &(*address).into(),
+                    self.base_asset,
+                    LOCAL_COINS,
+                    LOCAL_COIN_AMOUNT,
+                )
+            })
+            .collect();
+
+        let mut chain_config = ChainConfig::local_testnet();
+        chain_config
+            .consensus_parameters
+            .set_chain_id(self.chain_id.into());
+        chain_config
+            .consensus_parameters
+            .set_base_asset_id(self.base_asset);
+
+        setup_test_provider(
+            coins,
+            vec![],
+            Some(NodeConfig::default()),
+            Some(chain_config),
+        )
+        .await
+    }
+}
@@ -0,0 +1,315 @@
+//! What an address owns on the marketplace, printed by `portfolio`
+//!
+//! NFTs are the assets held exactly once, or minted by the collection of the manifest, that are
+//! not ask assets. Listings come from the indexer's order book, which knows their status, and
+//! the balances shown are those of the assets the listings ask for and of the base asset. Open
+//! offers are the offers of the order book made by the address or for one of its NFTs whose
+//! predicate still holds the payment
+
+use crate::manifest::Manifest;
+use fuels::{
+    prelude::{Address, AssetId, Bech32Address, ContractId, Provider},
+    types::{errors::Error, Bytes32},
+};
+use nullstate_indexer::store::IndexedListing;
+use nullstate_sdk::{collection::get_asset_id, offer::Offer, serde_hex};
+use serde::Serialize;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+/// An NFT held by the address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeldNft {
+    #[serde(with = "serde_hex")]
+    pub asset_id: AssetId,
+    /// Known when the token was minted through the manifest's collection
+    #[serde(with = "serde_hex::option")]
+    pub contract_id: Option<ContractId>,
+    #[serde(with = "serde_hex::option")]
+    pub sub_id: Option<Bytes32>,
+}
+
+/// A listing paying the address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReceiverListing {
+    #[serde(with = "serde_hex")]
+    pub order_hash: Bytes32,
+    #[serde(with = "serde_hex")]
+    pub predicate_address: Address,
+    #[serde(with = "serde_hex")]
+    pub nft_asset_id: AssetId,
+    #[serde(with = "serde_hex")]
+    pub ask_asset: AssetId,
+    pub price: u64,
+    /// `pending`, `live`, `filled` or `cancelled`, as the indexer last saw it
+    pub status: &'static str,
+}
+
+/// An offer whose predicate still holds the payment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OpenOffer {
+    #[serde(with = "serde_hex")]
+    pub predicate_address: Address,
+    /// The amount of the ask asset the predicate holds
+    pub locked: u64,
+    pub offer: Offer,
+}
+
+/// The balance of an ask asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AskBalance {
+    #[serde(with = "serde_hex")]
+    pub asset_id: AssetId,
+    pub amount: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Portfolio {
+    #[serde(with = "serde_hex")]
+    pub address: Address,
+    pub nfts: Vec<HeldNft>,
+    pub listings: Vec<ReceiverListing>,
+    /// Offers the address made as a bidder
+    pub offers_made: Vec<OpenOffer>,
+    /// Offers for NFTs the address holds
+    pub offers_received: Vec<OpenOffer>,
+    pub balances: Vec<AskBalance>,
+}
+
+/// The NFTs in `held`, with the collection and sub id of those the manifest records
+fn held_nfts(
+    held: &HashMap<AssetId, u128>,
+    ask_assets: &BTreeSet<AssetId>,
+    manifest: Option<&Manifest>,
+) -> Vec<HeldNft> {
+    // The asset ids are derived again rather than read from the records, so only tokens of the
+    // collection itself are resolved
+    let minted: HashMap<AssetId, (ContractId, Bytes32)> = manifest
+        .map(|manifest| {
+            let contract_id = manifest.collection.contract_id;
+            manifest
+                .mints
+                .iter()
+                .map(|mint| {
+                    (
+                        get_asset_id(mint.sub_id, contract_id),
+                        (contract_id, mint.sub_id),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut nfts: Vec<HeldNft> = held
+        .iter()
+        .filter(|(asset_id, amount)| {
+            !ask_assets.contains(asset_id)
+                && ((minted.contains_key(asset_id) && **amount > 0) || **amount == 1)
+        })
+        .map(|(asset_id, _)| {
+            let token = minted.get(asset_id);
+            HeldNft {
+                asset_id: *asset_id,
+                contract_id: token.map(|(contract_id, _)| *contract_id),
+                sub_id: token.map(|(_, sub_id)| *sub_id),
+            }
+        })
+        .collect();
+    nfts.sort_by_key(|nft| nft.asset_id);
+
+    nfts
+}
+
+/// The assets whose balance is shown, the base asset and those `listings` ask for
+fn ask_assets(base_asset: AssetId, listings: &[IndexedListing]) -> BTreeSet<AssetId> {
+    std::iter::once(base_asset)
+        .chain(
+            listings
+                .iter()
+                .map(|listing| listing.order.listing.ask_asset),
+        )
+        .collect()
+}
+
+/// Whether `offer` was made by `address` or is for one of `nfts`
+fn offer_concerns(offer: &Offer, address: Address, nfts: &[HeldNft]) -> bool {
+    offer.receiver() == address
+        || nfts
+            .iter()
+            .any(|nft| offer.accepts(nft.asset_id, nft.contract_id))
+}
+
+impl Portfolio {
+    /// Sorts what `address` holds, the listings it is the receiver of and the offers concerning it
+    ///
+    /// `offers` pairs every offer with the amount of its ask asset its predicate holds, the
+    /// offers that are not open or do not concern the address are left out
+    pub fn new(
+        address: Address,
+        held: &HashMap<AssetId, u128>,
+        base_asset: AssetId,
+        manifest: Option<&Manifest>,
+        listings: &[IndexedListing],
+        offers: &[(Offer, u64)],
+    ) -> Self {
+        let ask_assets = ask_assets(base_asset, listings);
+        let nfts = held_nfts(held, &ask_assets, manifest);
+
+        let open_offers = |made: bool| {
+            offers
+                .iter()
+                .filter(|(offer, locked)| offer.is_open(*locked))
+                .filter(|(offer, _)| {
+                    if made {
+                        offer.receiver() == address
+                    } else {
+                        offer.receiver() != address && offer_concerns(offer, address, &nfts)
+                    }
+                })
+                .map(|(offer, locked)| OpenOffer {
+                    predicate_address: offer.predicate_address(),
+                    locked: *locked,
+                    offer: offer.clone(),
+                })
+                .collect()
+        };
+        let offers_made = open_offers(true);
+        let offers_received = open_offers(false);
+
+        Self {
+            address,
+            nfts,
+            offers_made,
+            offers_received,
+            listings: listings
+                .iter()
+                .filter(|listing| listing.order.listing.receiver == address)
+                .map(|listing| ReceiverListing {
+                    order_hash: listing.order.hash(),
+                    predicate_address: listing.order.predicate_address,
+                    nft_asset_id: listing.order.listing.nft_asset_id,
+                    ask_asset: listing.order.listing.ask_asset,
+                    price: listing.order.listing.ask_amount,
+                    status: listing.status.as_str(),
+                })
+                .collect(),
+            balances: ask_assets
+                .into_iter()
+                .map(|asset_id| AskBalance {
+                    asset_id,
+                    amount: held.get(&asset_id).copied().unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads the balances of `address`, and of the predicates of the offers concerning it, from
+    /// the node
+    pub async fn fetch(
+        provider: &Provider,
+        address: Address,
+        manifest: Option<&Manifest>,
+        listings: &[IndexedListing],
+        offers: &[Offer],
+    ) -> Result<Self, Error> {
+        let base_asset = *provider.consensus_parameters().await?.base_asset_id();
+        let held = provider
+            .get_balances(&Bech32Address::from(address))
+            .await?
+            .into_iter()
+            .filter_map(|(asset_id, amount)| {
+                Some((asset_id.parse::<AssetId>().ok()?, u128::from(amount)))
+            })
+            .collect();
+
+        let nfts = held_nfts(&held, &ask_assets(base_asset, listings), manifest);
+        let mut locked = vec![];
+        for offer in offers {
+            if !offer_concerns(offer, address, &nfts) {
+                continue;
+            }
+            let amount = provider
+                .get_asset_balance(
+                    &Bech32Address::from(offer.predicate_address()),
+                    offer.ask_asset(),
+                )
+                .await?;
+            locked.push((offer.clone(), amount));
+        }
+
+        Ok(Self::new(
+            address, &held, base_asset, manifest, listings, &locked,
+        ))
+    }
+}
+
+impl fmt::Display for Portfolio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "address {:#x}", self.address)?;
+
+        writeln!(f, "nfts")?;
+        if self.nfts.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for nft in &self.nfts {
+            write!(f, "  {:#x}", nft.asset_id)?;
+            if let (Some(contract_id), Some(sub_id)) = (nft.contract_id, nft.sub_id) {
+                write!(f, "  collection {contract_id:#x} sub id {sub_id:#x}")?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "listings")?;
+        if self.listings.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for listing in &self.listings {
+            writeln!(
+                f,
+                "  {:<9} {:#x}  {} of {:#x}  at {:#x}",
+                listing.status,
+                listing.nft_asset_id,
+                listing.price,
+                listing.ask_asset,
+                listing.predicate_address
+            )?;
+        }
+
+        for (title, offers) in [
+            ("offers made", &self.offers_made),
+            ("offers received", &self.offers_received),
+        ] {
+            writeln!(f, "{title}")?;
+            if offers.is_empty() {
+                writeln!(f, "  none")?;
+            }
+            for open in offers {
+                match &open.offer {
+                    Offer::Nft(offer) => write!(f, "  nft        {:#x}", offer.nft_asset_id)?,
+                    Offer::Collection(offer) => {
+                        write!(f, "  collection {:#x}", offer.nft_contract_id)?
+                    }
+                }
+                writeln!(
+                    f,
+                    "  {} of {:#x}  at {:#x}",
+                    open.locked,
+                    open.offer.ask_asset(),
+                    open.predicate_address
+                )?;
+            }
+        }
+
+        writeln!(f, "balances")?;
+        for (index, balance) in self.balances.iter().enumerate() {
+            write!(f, "  {:#x} {}", balance.asset_id, balance.amount)?;
+            if index + 1 < self.balances.len() {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
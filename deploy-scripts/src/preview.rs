@@ -0,0 +1,186 @@
+//! What a transaction would do, printed by `--dry-run` instead of broadcasting it
+//!
+//! The transaction is run against the node without being committed, so predicates are
+//! checked and the fee is estimated just as they would be for the real submission
+
+use fuels::{
+    prelude::{AssetId, Provider},
+    tx::{Input, Output},
+    types::{errors::Error, transaction::Transaction, Bytes32},
+};
+use std::fmt;
+
+/// One input or output of the transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// `coin`, `predicate`, `message`, `contract`, `change`, `variable` or `created`
+    pub kind: &'static str,
+    /// The owner or recipient, or the contract
+    pub account: Option<Bytes32>,
+    pub asset_id: Option<AssetId>,
+    /// Unknown for change and variable outputs until the transaction runs
+    pub amount: Option<u64>,
+}
+
+/// How the dry run went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Success {
+        gas_used: u64,
+        total_fee: u64,
+    },
+    /// The node rejected the transaction or the script reverted
+    Failed(String),
+}
+
+/// The breakdown of a transaction and the result of running it without committing it
+#[derive(Debug, Clone)]
+pub struct TxPreview {
+    pub tx_id: Bytes32,
+    pub inputs: Vec<Entry>,
+    pub outputs: Vec<Entry>,
+    pub outcome: Outcome,
+}
+
+impl Entry {
+    fn from_input(input: &Input, base_asset: &AssetId) -> Self {
+        let kind = if input.is_contract() {
+            "contract"
+        } else if input.predicate().is_some() {
+            "predicate"
+        } else if input.is_coin() {
+            "coin"
+        } else {
+            "message"
+        };
+        let account = match input.contract_id() {
+            Some(contract_id) => Some(Bytes32::new(**contract_id)),
+            None => input.input_owner().map(|owner| Bytes32::new(**owner)),
+        };
+
+        Self {
+            kind,
+            account,
+            asset_id: input.asset_id(base_asset).copied(),
+            amount: input.amount(),
+        }
+    }
+
+    fn from_output(output: &Output) -> Self {
+        let (kind, account, asset_id, amount) = match *output {
+            Output::Coin {
+                to,
+                amount,
+                asset_id,
+            } => ("coin", Some(*to), Some(asset_id), Some(amount)),
+            Output::Change { to, asset_id, .. } => ("change", Some(*to), Some(asset_id), None),
+            Output::Variable { to, asset_id, .. } => ("variable", Some(*to), Some(asset_id), None),
+            Output::Contract(_) => ("contract", None, None, None),
+            Output::ContractCreated { contract_id, .. } => {
+                ("created", Some(*contract_id), None, None)
+            }
+        };
+
+        Self {
+            kind,
+            account: account.map(Bytes32::new),
+            asset_id,
+            amount,
+        }
+    }
+}
+
+impl TxPreview {
+    /// Runs `tx` on the node without committing it
+    ///
+    /// A transaction the node refuses still gets a preview, with the reason as its outcome.
+    /// Only failing to reach the node is an error
+    pub async fn dry_run<T: Transaction + Clone>(
+        provider: &Provider,
+        tx: T,
+    ) -> Result<Self, Error> {
+        let consensus_parameters = provider.consensus_parameters().await?;
+        let base_asset = *consensus_parameters.base_asset_id();
+        let inputs = tx
+            .inputs()
+            .iter()
+            .map(|input| Entry::from_input(input, &base_asset))
+            .collect();
+        let outputs = tx.outputs().iter().map(Entry::from_output).collect();
+        let tx_id = tx.id(consensus_parameters.chain_id());
+
+        let outcome = match provider
+            .dry_run(tx.clone())
+            .await
+            .and_then(|status| status.check(None))
+        {
+            Ok(()) => {
+                let cost = provider.estimate_transaction_cost(tx, None, None).await?;
+                Outcome::Success {
+                    gas_used: cost.total_gas,
+                    total_fee: cost.total_fee,
+                }
+            }
+            Err(e) => Outcome::Failed(e.to_string()),
+        };
+
+        Ok(Self {
+            tx_id,
+            inputs,
+            outputs,
+            outcome,
+        })
+    }
+
+    /// The predicate inputs, each checked by the node during the dry run
+    pub fn predicate_inputs(&self) -> usize {
+        self.inputs
+            .iter()
+            .filter(|input| input.kind == "predicate")
+            .count()
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<10}", self.kind)?;
+        if let Some(account) = self.account {
+            write!(f, " {account:#x}")?;
+        }
+        match (self.amount, self.asset_id) {
+            (Some(amount), Some(asset_id)) => write!(f, "  {amount} of {asset_id:#x}"),
+            (None, Some(asset_id)) => write!(f, "  remainder of {asset_id:#x}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for TxPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transaction {:#x}", self.tx_id)?;
+        writeln!(f, "inputs")?;
+        for (index, input) in self.inputs.iter().enumerate() {
+            writeln!(f, "  [{index}] {input}")?;
+        }
+        writeln!(f, "outputs")?;
+        for (index, output) in self.outputs.iter().enumerate() {
+            writeln!(f, "  [{index}] {output}")?;
+        }
+
+        match &self.outcome {
+            Outcome::Success {
+                gas_used,
+                total_fee,
+            } => {
+                if self.predicate_inputs() > 0 {
+                    writeln!(f, "predicates {} input(s) passed", self.predicate_inputs())?;
+                }
+                writeln!(f, "fee        {total_fee} (gas used {gas_used})")?;
+                write!(f, "dry run succeeded, nothing was broadcast")
+            }
+            Outcome::Failed(reason) => {
+                write!(f, "dry run failed, nothing was broadcast: {reason}")
+            }
+        }
+    }
+}
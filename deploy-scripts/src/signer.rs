@@ -0,0 +1,192 @@
+//! Where the CLI gets the key it signs with
+//!
+//! Keys never appear in source or on the command line: they come from an encrypted JSON
+//! keystore, a mnemonic phrase or a raw secret key, the last two read from the environment
+
+use clap::Args;
+use fuels::{
+    accounts::wallet::WalletUnlocked, crypto::SecretKey, prelude::Provider, types::errors::Error,
+};
+use std::{fmt, path::PathBuf};
+
+/// Environment variable holding a raw secret key, used when no other source is given
+pub const SECRET_KEY_ENV: &str = "NULLSTATE_SECRET_KEY";
+/// Environment variable holding the mnemonic phrase read by `--mnemonic`
+pub const MNEMONIC_ENV: &str = "NULLSTATE_MNEMONIC";
+/// Environment variable holding the keystore passphrase, prompted for when unset
+pub const KEYSTORE_PASSWORD_ENV: &str = "NULLSTATE_KEYSTORE_PASSWORD";
+/// The first account of the Fuel derivation path
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/1179993420'/0'/0/0";
+
+/// How the signer is chosen on the command line
+#[derive(Debug, Clone, Args)]
+pub struct SignerArgs {
+    /// Encrypted JSON keystore of the signer, unlocked with the passphrase in
+    /// NULLSTATE_KEYSTORE_PASSWORD or prompted for
+    #[arg(long, global = true, conflicts_with = "mnemonic")]
+    pub keystore: Option<PathBuf>,
+    /// Derives the signer from the mnemonic phrase in NULLSTATE_MNEMONIC
+    #[arg(long, global = true)]
+    pub mnemonic: bool,
+    /// Derivation path of the signer within the mnemonic
+    #[arg(long, global = true, default_value = DEFAULT_DERIVATION_PATH)]
+    pub derivation_path: String,
+}
+
+/// Where the keystore passphrase comes from
+#[derive(Clone)]
+pub enum Passphrase {
+    Given(String),
+    /// Asked for on the terminal when the keystore is loaded
+    Prompt,
+}
+
+/// A configured signing key, loaded into a wallet only when a command needs to sign
+#[derive(Clone)]
+pub enum SignerSource {
+    Keystore {
+        path: PathBuf,
+        passphrase: Passphrase,
+    },
+    Mnemonic {
+        phrase: String,
+        derivation_path: String,
+    },
+    SecretKey(SecretKey),
+}
+
+/// Why no signer could be loaded
+#[derive(Debug)]
+pub enum SignerError {
+    /// `--mnemonic` was given without a phrase in `MNEMONIC_ENV`
+    MissingMnemonic,
+    /// `SECRET_KEY_ENV` does not hold a secret key
+    InvalidSecretKey,
+    /// The keystore is missing, malformed or the passphrase is wrong
+    Keystore(PathBuf, Error),
+    /// The phrase or the derivation path is invalid
+    Mnemonic(Error),
+    /// The passphrase could not be read from the terminal
+    Prompt(std::io::Error),
+}
+
+impl fmt::Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMnemonic => write!(f, "--mnemonic requires {MNEMONIC_ENV} to be set"),
+            Self::InvalidSecretKey => write!(f, "{SECRET_KEY_ENV} is not a valid secret key"),
+            Self::Keystore(path, e) => {
+                write!(f, "cannot unlock keystore {}: {e}", path.display())
+            }
+            Self::Mnemonic(e) => write!(f, "cannot derive signer from mnemonic: {e}"),
+            Self::Prompt(e) => write!(f, "cannot read passphrase: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Given(_) => write!(f, "Given(..)"),
+            Self::Prompt => write!(f, "Prompt"),
+        }
+    }
+}
+
+impl fmt::Debug for SignerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keystore { path, passphrase } => f
+                .debug_struct("Keystore")
+                .field("path", path)
+                .field("passphrase", passphrase)
+                .finish(),
+            Self::Mnemonic {
+                derivation_path, ..
+            } => f
+                .debug_struct("Mnemonic")
+                .field("derivation_path", derivation_path)
+                .finish_non_exhaustive(),
+            Self::SecretKey(_) => write!(f, "SecretKey(..)"),
+        }
+    }
+}
+
+impl SignerSource {
+    /// Picks the source selected by `args`, reading secrets with `env`
+    ///
+    /// A keystore or a mnemonic must be asked for explicitly, otherwise the raw key in
+    /// `SECRET_KEY_ENV` is used. Returns `None` when no source is configured at all
+    pub fn resolve(
+        args: &SignerArgs,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, SignerError> {
+        if let Some(path) = &args.keystore {
+            let passphrase = match env(KEYSTORE_PASSWORD_ENV) {
+                Some(passphrase) => Passphrase::Given(passphrase),
+                None => Passphrase::Prompt,
+            };
+
+            return Ok(Some(Self::Keystore {
+                path: path.clone(),
+                passphrase,
+            }));
+        }
+
+        if args.mnemonic {
+            let phrase = env(MNEMONIC_ENV).ok_or(SignerError::MissingMnemonic)?;
+
+            return Ok(Some(Self::Mnemonic {
+                phrase,
+                derivation_path: args.derivation_path.clone(),
+            }));
+        }
+
+        env(SECRET_KEY_ENV)
+            .map(|secret_key| {
+                secret_key
+                    .trim()
+                    .parse()
+                    .map(Self::SecretKey)
+                    .map_err(|_| SignerError::InvalidSecretKey)
+            })
+            .transpose()
+    }
+
+    /// `resolve` reading the process environment
+    pub fn from_env(args: &SignerArgs) -> Result<Option<Self>, SignerError> {
+        Self::resolve(args, |name| std::env::var(name).ok())
+    }
+
+    /// Unlocks the key, prompting for the keystore passphrase if it was not given
+    pub fn load(&self, provider: Option<Provider>) -> Result<WalletUnlocked, SignerError> {
+        match self {
+            Self::Keystore { path, passphrase } => {
+                let passphrase = match passphrase {
+                    Passphrase::Given(passphrase) => passphrase.clone(),
+                    Passphrase::Prompt => {
+                        rpassword::prompt_password(format!("Passphrase of {}: ", path.display()))
+                            .map_err(SignerError::Prompt)?
+                    }
+                };
+
+                WalletUnlocked::load_keystore(path, passphrase, provider)
+                    .map_err(|e| SignerError::Keystore(path.clone(), e))
+            }
+            Self::Mnemonic {
+                phrase,
+                derivation_path,
+            } => WalletUnlocked::new_from_mnemonic_phrase_with_path(
+                phrase,
+                provider,
+                derivation_path,
+            )
+            .map_err(SignerError::Mnemonic),
+            Self::SecretKey(secret_key) => {
+                Ok(WalletUnlocked::new_from_private_key(*secret_key, provider))
+            }
+        }
+    }
+}
@@ -0,0 +1,1583 @@
+use deploy_scripts::signer::{
+    Passphrase, SignerArgs, SignerError, SignerSource, DEFAULT_DERIVATION_PATH,
+    KEYSTORE_PASSWORD_ENV, MNEMONIC_ENV, SECRET_KEY_ENV,
+};
+use fuels::{accounts::wallet::WalletUnlocked, crypto::SecretKey};
+use std::{collections::HashMap, path::PathBuf};
+
+const KEYSTORE: &str = "tests/fixtures/keystore.json";
+const KEYSTORE_PASSPHRASE: &str = "nullstate fixture passphrase";
+/// The key encrypted in the fixture keystore
+const KEYSTORE_SECRET_KEY: &str =
+    "0xb20265198fb6ed79c330671e7bbd79c6cd06ccdff248e35f2c744ea07bbdff15";
+const MNEMONIC: &str =
+    "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+fn args(keystore: Option<&str>, mnemonic: bool, derivation_path: &str) -> SignerArgs {
+    SignerArgs {
+        keystore: keystore.map(PathBuf::from),
+        mnemonic,
+        derivation_path: derivation_path.to_string(),
+    }
+}
+
+// Resolves the signer of `args` against an environment holding only `vars`
+fn resolve(args: &SignerArgs, vars: &[(&str, &str)]) -> Result<Option<SignerSource>, SignerError> {
+    let env: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+    SignerSource::resolve(args, |name| env.get(name).cloned())
+}
+
+fn expected_wallet(secret_key: &str) -> WalletUnlocked {
+    WalletUnlocked::new_from_private_key(secret_key.parse::<SecretKey>().unwrap(), None)
+}
+
+mod signer {
+
+    use super::*;
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn keystore_with_passphrase_from_env() {
+            let source = resolve(
+                &args(Some(KEYSTORE), false, DEFAULT_DERIVATION_PATH),
+                &[(KEYSTORE_PASSWORD_ENV, KEYSTORE_PASSPHRASE)],
+            )
+            .unwrap()
+            .unwrap();
+
+            let wallet = source.load(None).unwrap();
+            assert_eq!(
+                wallet.address(),
+                expected_wallet(KEYSTORE_SECRET_KEY).address()
+            );
+        }
+
+        #[test]
+        fn keystore_without_passphrase_prompts() {
+            let source = resolve(&args(Some(KEYSTORE), false, DEFAULT_DERIVATION_PATH), &[])
+                .unwrap()
+                .unwrap();
+
+            assert!(matches!(
+                source,
+                SignerSource::Keystore {
+                    passphrase: Passphrase::Prompt,
+                    ..
+                }
+            ));
+        }
+
+        #[test]
+        fn keystore_takes_precedence_over_secret_key() {
+            let source = resolve(
+                &args(Some(KEYSTORE), false, DEFAULT_DERIVATION_PATH),
+                &[
+                    (KEYSTORE_PASSWORD_ENV, KEYSTORE_PASSPHRASE),
+                    (SECRET_KEY_ENV, &format!("0x{}", "11".repeat(32))),
+                ],
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(matches!(source, SignerSource::Keystore { .. }));
+        }
+
+        #[test]
+        fn mnemonic_with_default_path() {
+            let source = resolve(
+                &args(None, true, DEFAULT_DERIVATION_PATH),
+                &[(MNEMONIC_ENV, MNEMONIC)],
+            )
+            .unwrap()
+            .unwrap();
+
+            let wallet = source.load(None).unwrap();
+            let expected = WalletUnlocked::new_from_mnemonic_phrase(MNEMONIC, None).unwrap();
+            assert_eq!(wallet.address(), expected.address());
+        }
+
+        #[test]
+        fn mnemonic_derivation_path_selects_account() {
+            let wallet = |path: &str| {
+                resolve(&args(None, true, path), &[(MNEMONIC_ENV, MNEMONIC)])
+                    .unwrap()
+                    .unwrap()
+                    .load(None)
+                    .unwrap()
+            };
+
+            let first = wallet(DEFAULT_DERIVATION_PATH);
+            let second = wallet("m/44'/1179993420'/1'/0/0");
+            assert_ne!(first.address(), second.address());
+        }
+
+        #[test]
+        fn secret_key_from_env() {
+            let source = resolve(
+                &args(None, false, DEFAULT_DERIVATION_PATH),
+                &[(SECRET_KEY_ENV, KEYSTORE_SECRET_KEY)],
+            )
+            .unwrap()
+            .unwrap();
+
+            assert_eq!(
+                source.load(None).unwrap().address(),
+                expected_wallet(KEYSTORE_SECRET_KEY).address()
+            );
+        }
+
+        #[test]
+        fn no_signer_configured() {
+            assert!(resolve(&args(None, false, DEFAULT_DERIVATION_PATH), &[])
+                .unwrap()
+                .is_none());
+        }
+
+        #[test]
+        fn secrets_are_not_printed() {
+            let source = resolve(
+                &args(None, true, DEFAULT_DERIVATION_PATH),
+                &[(MNEMONIC_ENV, MNEMONIC)],
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(!format!("{source:?}").contains("oblige"));
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn wrong_passphrase() {
+            let source = resolve(
+                &args(Some(KEYSTORE), false, DEFAULT_DERIVATION_PATH),
+                &[(KEYSTORE_PASSWORD_ENV, "not the passphrase")],
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(matches!(source.load(None), Err(SignerError::Keystore(..))));
+        }
+
+        #[test]
+        fn missing_keystore() {
+            let source = resolve(
+                &args(
+                    Some("tests/fixtures/missing.json"),
+                    false,
+                    DEFAULT_DERIVATION_PATH,
+                ),
+                &[(KEYSTORE_PASSWORD_ENV, KEYSTORE_PASSPHRASE)],
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(matches!(source.load(None), Err(SignerError::Keystore(..))));
+        }
+
+        #[test]
+        fn mnemonic_flag_without_phrase() {
+            assert!(matches!(
+                resolve(&args(None, true, DEFAULT_DERIVATION_PATH), &[]),
+                Err(SignerError::MissingMnemonic)
+            ));
+        }
+
+        #[test]
+        fn invalid_derivation_path() {
+            let source = resolve(&args(None, true, "not a path"), &[(MNEMONIC_ENV, MNEMONIC)])
+                .unwrap()
+                .unwrap();
+
+            assert!(matches!(source.load(None), Err(SignerError::Mnemonic(_))));
+        }
+
+        #[test]
+        fn invalid_secret_key() {
+            assert!(matches!(
+                resolve(
+                    &args(None, false, DEFAULT_DERIVATION_PATH),
+                    &[(SECRET_KEY_ENV, "0x1234")]
+                ),
+                Err(SignerError::InvalidSecretKey)
+            ));
+        }
+    }
+}
+
+mod network {
+
+    use super::*;
+    use deploy_scripts::network::{NetworkConfig, NetworkError};
+    use fuels::{
+        accounts::ViewOnlyAccount,
+        prelude::{launch_provider_and_get_wallet, Address, AssetId},
+    };
+    use nullstate_sdk::fee::FeePolicy;
+
+    const CUSTOM_CONFIG: &str = r#"
+        [devnet]
+        node_url = "127.0.0.1:4000"
+        chain_id = 7
+        base_asset = "0x0101010101010101010101010101010101010101010101010101010101010101"
+        treasury = "0x0202020202020202020202020202020202020202020202020202020202020202"
+        fee_policy = { bps = 250 }
+    "#;
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn bundled_profiles() {
+            let config = NetworkConfig::default();
+
+            let local = config.profile("local").unwrap();
+            assert_eq!(local.node_url, None);
+            assert_eq!(local.base_asset, AssetId::zeroed());
+
+            let testnet = config.profile("testnet").unwrap();
+            assert!(testnet.node_url.is_some());
+            assert!(testnet.treasury.is_some());
+
+            let mainnet = config.profile("mainnet").unwrap();
+            assert_eq!(mainnet.chain_id, 9889);
+        }
+
+        #[test]
+        fn custom_profile() {
+            let config: NetworkConfig = CUSTOM_CONFIG.parse().unwrap();
+            let devnet = config.profile("devnet").unwrap();
+
+            assert_eq!(devnet.chain_id, 7);
+            assert_eq!(devnet.base_asset, AssetId::new([1u8; 32]));
+            assert_eq!(devnet.treasury, Some(Address::new([2u8; 32])));
+            assert_eq!(devnet.fee_policy, FeePolicy::Bps(250));
+        }
+
+        #[tokio::test]
+        async fn local_profile_runs_node_in_process() {
+            let local = NetworkConfig::default().profile("local").unwrap().clone();
+            let signer = expected_wallet(KEYSTORE_SECRET_KEY);
+            let signer_address = Address::from(signer.address());
+
+            let provider = local.connect(&[signer_address]).await.unwrap();
+            let consensus_parameters = provider.consensus_parameters().await.unwrap();
+
+            assert_eq!(u64::from(consensus_parameters.chain_id()), local.chain_id);
+            assert!(
+                provider
+                    .get_asset_balance(signer.address(), local.base_asset)
+                    .await
+                    .unwrap()
+                    > 0
+            );
+        }
+
+        #[tokio::test]
+        async fn local_profile_with_custom_chain() {
+            let mut local = NetworkConfig::default().profile("local").unwrap().clone();
+            local.chain_id = 42;
+            local.base_asset = AssetId::new([9u8; 32]);
+
+            let provider = local.connect(&[]).await.unwrap();
+            let consensus_parameters = provider.consensus_parameters().await.unwrap();
+            assert_eq!(u64::from(consensus_parameters.chain_id()), 42);
+            assert_eq!(
+                *consensus_parameters.base_asset_id(),
+                AssetId::new([9u8; 32])
+            );
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn unknown_network() {
+            assert!(matches!(
+                NetworkConfig::default().profile("devnet"),
+                Err(NetworkError::UnknownNetwork { .. })
+            ));
+        }
+
+        #[test]
+        fn unknown_field() {
+            let config = CUSTOM_CONFIG.replace("fee_policy", "fees");
+
+            assert!(matches!(
+                config.parse::<NetworkConfig>(),
+                Err(NetworkError::Toml(_))
+            ));
+        }
+
+        #[test]
+        fn malformed_asset_id() {
+            let config = CUSTOM_CONFIG.replace("0x0101", "0xzz01");
+
+            assert!(matches!(
+                config.parse::<NetworkConfig>(),
+                Err(NetworkError::Toml(_))
+            ));
+        }
+
+        #[test]
+        fn fee_above_whole_price() {
+            let config = CUSTOM_CONFIG.replace("bps = 250", "bps = 10001");
+
+            assert!(matches!(
+                config.parse::<NetworkConfig>(),
+                Err(NetworkError::FeeAboveWholePrice { bps: 10_001, .. })
+            ));
+        }
+
+        #[tokio::test]
+        async fn node_runs_other_chain() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let mut profile = NetworkConfig::default().profile("local").unwrap().clone();
+            profile.node_url = Some(wallet.provider().unwrap().url().to_string());
+            profile.chain_id += 1;
+
+            assert!(matches!(
+                profile.connect(&[]).await,
+                Err(NetworkError::ChainIdMismatch { .. })
+            ));
+        }
+    }
+}
+
+mod manifest {
+
+    use deploy_scripts::manifest::{
+        manifest_path, CollectionDeployment, Manifest, ManifestError, MintRecord,
+        PredicateConfigurables, PredicateDeployment, MANIFEST_VERSION,
+    };
+    use fuels::{
+        prelude::{Address, AssetId, ContractId},
+        types::Bytes32,
+    };
+    use nullstate_sdk::{fee::FeePolicy, predicate::bytecode_hash};
+    use std::{fs, path::PathBuf};
+
+    // A directory of its own for every test, tests run in parallel
+    fn deployments_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "nullstate-deployments-{}-{test}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn manifest() -> Manifest {
+        Manifest {
+            version: MANIFEST_VERSION,
+            network: "local".to_string(),
+            chain_id: 0,
+            collection: CollectionDeployment {
+                contract_id: ContractId::new([1u8; 32]),
+                owner: Address::new([2u8; 32]),
+                salt: Bytes32::new([7u8; 32]),
+                deploy_tx: None,
+                constructor_tx: Some(Bytes32::new([3u8; 32])),
+            },
+            predicate: PredicateDeployment {
+                bytecode_hash: bytecode_hash(b"predicate"),
+                configurables: PredicateConfigurables::new(
+                    Some(Address::new([4u8; 32])),
+                    FeePolicy::Bps(250),
+                ),
+            },
+            mints: vec![MintRecord {
+                sub_id: Bytes32::new([5u8; 32]),
+                asset_id: AssetId::new([6u8; 32]),
+                recipient: Address::new([2u8; 32]),
+                amount: 1,
+                tx_id: None,
+            }],
+        }
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let path = manifest_path(&deployments_dir("round_trip"), "local");
+            manifest().save(&path).unwrap();
+
+            assert_eq!(Manifest::load(&path).unwrap(), Some(manifest()));
+            assert_eq!(Manifest::load_deployed(&path, 0).unwrap(), manifest());
+        }
+
+        #[test]
+        fn ids_are_hex_strings() {
+            let path = manifest_path(&deployments_dir("ids_are_hex_strings"), "local");
+            manifest().save(&path).unwrap();
+
+            let json: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(
+                json["collection"]["contract_id"],
+                format!("0x{}", "01".repeat(32))
+            );
+            assert_eq!(json["predicate"]["configurables"]["FEE_BPS"], 250);
+            assert_eq!(json["predicate"]["configurables"]["FEE_AMOUNT"], 0);
+        }
+
+        #[test]
+        fn no_manifest_yet() {
+            let path = manifest_path(&deployments_dir("no_manifest_yet"), "local");
+
+            assert_eq!(Manifest::load(&path).unwrap(), None);
+            Manifest::ensure_can_deploy(&path, false).unwrap();
+        }
+
+        #[test]
+        fn force_replaces_deployment() {
+            let path = manifest_path(&deployments_dir("force_replaces_deployment"), "local");
+            manifest().save(&path).unwrap();
+
+            Manifest::ensure_can_deploy(&path, true).unwrap();
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn redeploy_without_force() {
+            let path = manifest_path(&deployments_dir("redeploy_without_force"), "local");
+            manifest().save(&path).unwrap();
+
+            assert!(matches!(
+                Manifest::ensure_can_deploy(&path, false),
+                Err(ManifestError::AlreadyDeployed(_))
+            ));
+        }
+
+        #[test]
+        fn not_deployed() {
+            let path = manifest_path(&deployments_dir("not_deployed"), "local");
+
+            assert!(matches!(
+                Manifest::load_deployed(&path, 0),
+                Err(ManifestError::NotDeployed(_))
+            ));
+        }
+
+        #[test]
+        fn other_chain() {
+            let path = manifest_path(&deployments_dir("other_chain"), "local");
+            manifest().save(&path).unwrap();
+
+            assert!(matches!(
+                Manifest::load_deployed(&path, 9889),
+                Err(ManifestError::WrongChain {
+                    expected: 9889,
+                    found: 0
+                })
+            ));
+        }
+
+        #[test]
+        fn unsupported_version() {
+            let path = manifest_path(&deployments_dir("unsupported_version"), "local");
+            Manifest {
+                version: MANIFEST_VERSION + 1,
+                ..manifest()
+            }
+            .save(&path)
+            .unwrap();
+
+            assert!(matches!(
+                Manifest::load(&path),
+                Err(ManifestError::UnsupportedVersion(_))
+            ));
+        }
+    }
+}
+
+mod preview {
+
+    use deploy_scripts::preview::{Outcome, TxPreview};
+    use fuels::{
+        accounts::{Account, ViewOnlyAccount},
+        prelude::{
+            launch_provider_and_get_wallet, AssetId, Bech32Address, BuildableTransaction, Provider,
+            ScriptTransactionBuilder, TxPolicies, WalletUnlocked,
+        },
+        types::{
+            output::Output, transaction::ScriptTransaction,
+            transaction_builders::TransactionBuilder,
+        },
+    };
+
+    const AMOUNT: u64 = 100;
+
+    async fn base_asset(provider: &Provider) -> AssetId {
+        *provider
+            .consensus_parameters()
+            .await
+            .unwrap()
+            .base_asset_id()
+    }
+
+    // Signs a transfer of `AMOUNT` base asset from `wallet`, paying `sent` to `recipient`
+    async fn transfer(
+        wallet: &WalletUnlocked,
+        recipient: &Bech32Address,
+        sent: u64,
+    ) -> (Provider, ScriptTransaction) {
+        let provider = wallet.provider().unwrap().clone();
+        let base_asset = base_asset(&provider).await;
+
+        let inputs = wallet
+            .get_asset_inputs_for_amount(base_asset, AMOUNT, None)
+            .await
+            .unwrap();
+        let outputs = vec![
+            Output::Coin {
+                to: recipient.into(),
+                amount: sent,
+                asset_id: base_asset,
+            },
+            Output::Change {
+                to: wallet.address().into(),
+                amount: 0,
+                asset_id: base_asset,
+            },
+        ];
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+        wallet.adjust_for_fee(&mut tb, AMOUNT.into()).await.unwrap();
+        tb.add_signer(wallet.clone()).unwrap();
+
+        let tx = tb.build(provider.clone()).await.unwrap();
+        (provider, tx)
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn transfer_is_not_broadcast() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let recipient = WalletUnlocked::new_random(None);
+            let (provider, tx) = transfer(&wallet, recipient.address(), AMOUNT).await;
+            let base_asset = base_asset(&provider).await;
+
+            let preview = TxPreview::dry_run(&provider, tx).await.unwrap();
+
+            assert!(matches!(preview.outcome, Outcome::Success { .. }));
+            assert!(preview.inputs.iter().all(|input| input.kind == "coin"));
+            assert_eq!(preview.outputs[0].kind, "coin");
+            assert_eq!(preview.outputs[0].amount, Some(AMOUNT));
+            assert_eq!(preview.outputs[0].asset_id, Some(base_asset));
+            assert_eq!(preview.outputs[1].kind, "change");
+            assert_eq!(preview.outputs[1].amount, None);
+            assert_eq!(preview.predicate_inputs(), 0);
+            assert!(preview.to_string().contains("nothing was broadcast"));
+
+            assert_eq!(
+                provider
+                    .get_asset_balance(recipient.address(), base_asset)
+                    .await
+                    .unwrap(),
+                0
+            );
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn outputs_above_inputs() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let recipient = WalletUnlocked::new_random(None);
+            let base_asset = base_asset(wallet.provider().unwrap()).await;
+            let balance = wallet.get_asset_balance(&base_asset).await.unwrap();
+            let (provider, tx) = transfer(&wallet, recipient.address(), balance + 1).await;
+
+            let preview = TxPreview::dry_run(&provider, tx).await.unwrap();
+
+            assert!(matches!(preview.outcome, Outcome::Failed(_)));
+            assert_eq!(preview.outputs[0].amount, Some(balance + 1));
+            assert!(preview.to_string().contains("dry run failed"));
+        }
+    }
+}
+
+mod deployment {
+
+    use fuels::{
+        accounts::ViewOnlyAccount,
+        prelude::{launch_provider_and_get_wallet, ContractId},
+        tx::StorageSlot,
+        types::{transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity},
+    };
+    use nullstate_sdk::collection::{deploy_collection, get_asset_id, CollectionConfig};
+
+    // Deploys `config` and checks it lands at the id predicted beforehand
+    async fn deploys_at_predicted_id(config: CollectionConfig) -> ContractId {
+        let wallet = launch_provider_and_get_wallet().await.unwrap();
+        let predicted = config.contract_id().unwrap();
+
+        let (contract_id, _) = deploy_collection(&wallet, &config).await.unwrap();
+
+        assert_eq!(contract_id, predicted);
+        contract_id
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn default_config() {
+            deploys_at_predicted_id(CollectionConfig::default()).await;
+        }
+
+        #[tokio::test]
+        async fn salted() {
+            deploys_at_predicted_id(CollectionConfig::new([1u8; 32])).await;
+        }
+
+        #[tokio::test]
+        async fn storage_slot_overrides() {
+            let slot = StorageSlot::new(Bytes32::new([7u8; 32]), Bytes32::new([1u8; 32]));
+            let config = CollectionConfig::new([1u8; 32]).with_storage_slots(vec![slot]);
+
+            let contract_id = deploys_at_predicted_id(config).await;
+
+            assert_ne!(
+                contract_id,
+                CollectionConfig::new([1u8; 32]).contract_id().unwrap()
+            );
+        }
+
+        #[test]
+        fn distinct_salts_give_distinct_ids() {
+            let first = CollectionConfig::new([1u8; 32]).contract_id().unwrap();
+            let second = CollectionConfig::new([2u8; 32]).contract_id().unwrap();
+
+            assert_ne!(first, second);
+            assert_eq!(
+                first,
+                CollectionConfig::new([1u8; 32]).contract_id().unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn minted_asset_id_is_predicted() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let config = CollectionConfig::new([3u8; 32]);
+            let sub_id = Bytes32::new([4u8; 32]);
+            let predicted = get_asset_id(sub_id, config.contract_id().unwrap());
+
+            let (_, instance) = deploy_collection(&wallet, &config).await.unwrap();
+            instance
+                .methods()
+                .constructor(Identity::Address(wallet.address().into()))
+                .call()
+                .await
+                .unwrap();
+            instance
+                .methods()
+                .mint(
+                    Identity::Address(wallet.address().into()),
+                    Bits256(*sub_id),
+                    1,
+                )
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .unwrap();
+
+            assert_eq!(wallet.get_asset_balance(&predicted).await.unwrap(), 1);
+        }
+    }
+}
+
+mod batch {
+
+    use deploy_scripts::batch::{
+        funding_transaction_builder, listings_per_transaction, parse_csv, parse_json, BatchError,
+        BatchNft, BatchTerms,
+    };
+    use fuels::{
+        accounts::ViewOnlyAccount,
+        prelude::{launch_provider_and_get_wallet, Address, AssetId, ContractId, WalletUnlocked},
+        types::{
+            transaction_builders::{
+                BuildableTransaction, TransactionBuilder, VariableOutputPolicy,
+            },
+            Bits256, Bytes32, Identity,
+        },
+    };
+    use nullstate_sdk::{
+        collection::{deploy_collection, CollectionConfig},
+        fee::FeePolicy,
+        order::{Order, OrderMetadata},
+        predicate::verify_order,
+    };
+
+    const TOKENS: u8 = 5;
+
+    fn sub_id(index: u8) -> Bytes32 {
+        Bytes32::new([index + 1; 32])
+    }
+
+    fn terms(receiver: Address, base_asset: AssetId, collection: Option<ContractId>) -> BatchTerms {
+        BatchTerms {
+            receiver,
+            treasury: Address::new([9u8; 32]),
+            fee: FeePolicy::Bps(250),
+            base_asset,
+            collection,
+        }
+    }
+
+    // Deploys a collection and mints `TOKENS` tokens of it to `wallet`
+    async fn mint_tokens(wallet: &WalletUnlocked) -> ContractId {
+        let (contract_id, instance) = deploy_collection(wallet, &CollectionConfig::default())
+            .await
+            .unwrap();
+        let owner = Identity::Address(wallet.address().into());
+        instance.methods().constructor(owner).call().await.unwrap();
+        for index in 0..TOKENS {
+            instance
+                .methods()
+                .mint(owner, Bits256(*sub_id(index)), 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .unwrap();
+        }
+
+        contract_id
+    }
+
+    // A CSV batch listing every minted token by its sub id
+    fn csv_batch() -> String {
+        let mut csv = "sub_id,price,ask_asset,expiry\n".to_string();
+        for index in 0..TOKENS {
+            let expiry = if index % 2 == 0 { "4000000000" } else { "" };
+            csv.push_str(&format!(
+                "{:#x},{},,{expiry}\n",
+                sub_id(index),
+                100 * (u64::from(index) + 1)
+            ));
+        }
+        csv
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn csv_and_json_agree() {
+            let json = format!(
+                r#"[{{"sub_id": "{:#x}", "price": 100, "expiry": 4000000000}},
+                    {{"asset_id": "{:#x}", "price": 200, "ask_asset": "{:#x}"}}]"#,
+                sub_id(0),
+                AssetId::new([3u8; 32]),
+                AssetId::new([4u8; 32]),
+            );
+            let csv = format!(
+                "sub_id,asset_id,price,ask_asset,expiry\n{:#x},,100,,4000000000\n,{:#x},200,{:#x},\n",
+                sub_id(0),
+                AssetId::new([3u8; 32]),
+                AssetId::new([4u8; 32]),
+            );
+
+            let entries = parse_json(&json).unwrap();
+
+            assert_eq!(entries, parse_csv(&csv).unwrap());
+            assert_eq!(entries[0].nft, BatchNft::SubId(sub_id(0)));
+            assert_eq!(entries[0].expiry, Some(4_000_000_000));
+            assert_eq!(entries[1].nft, BatchNft::AssetId(AssetId::new([3u8; 32])));
+            assert_eq!(entries[1].ask_asset, Some(AssetId::new([4u8; 32])));
+        }
+
+        #[test]
+        fn quoted_csv_cells() {
+            let csv = format!(
+                "\"sub_id\", price ,\"expiry\"\n\"{:#x}\",\"100\",\"\"\n",
+                sub_id(0)
+            );
+            let entries = parse_csv(&csv).unwrap();
+            assert_eq!(entries[0].nft, BatchNft::SubId(sub_id(0)));
+            assert_eq!(entries[0].price, 100);
+            assert_eq!(entries[0].expiry, None);
+        }
+
+        #[test]
+        fn fills_the_transaction_limits() {
+            assert_eq!(listings_per_transaction(255, 255), 127);
+            assert_eq!(listings_per_transaction(20, 255), 12);
+            assert_eq!(listings_per_transaction(0, 0), 1);
+        }
+
+        #[tokio::test]
+        async fn funds_every_listing_in_one_transaction() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let provider = wallet.provider().unwrap().clone();
+            let consensus_parameters = provider.consensus_parameters().await.unwrap();
+            let base_asset = *consensus_parameters.base_asset_id();
+            let chain_id = u64::from(consensus_parameters.chain_id());
+            let collection = mint_tokens(&wallet).await;
+
+            let entries = parse_csv(&csv_batch()).unwrap();
+            let listings = terms(wallet.address().into(), base_asset, Some(collection))
+                .listings(&entries)
+                .unwrap();
+            let tx_params = consensus_parameters.tx_params();
+            assert!(
+                listings_per_transaction(tx_params.max_inputs(), tx_params.max_outputs())
+                    >= listings.len()
+            );
+
+            let mut tb = funding_transaction_builder(&wallet, &listings)
+                .await
+                .unwrap();
+            tb.add_signer(wallet.clone()).unwrap();
+            let tx = tb.build(provider.clone()).await.unwrap();
+            provider
+                .send_transaction_and_await_commit(tx)
+                .await
+                .unwrap()
+                .check(None)
+                .unwrap();
+
+            let metadata = OrderMetadata {
+                created_at: 0,
+                block_height: provider.latest_block_height().await.unwrap(),
+                expires_at: None,
+            };
+            let orders: Vec<Order> = listings
+                .iter()
+                .zip(&entries)
+                .map(|(listing, entry)| {
+                    Order::from_listing(
+                        chain_id,
+                        listing.clone(),
+                        OrderMetadata {
+                            expires_at: entry.expiry,
+                            ..metadata
+                        },
+                    )
+                })
+                .collect();
+            let orders =
+                Order::many_from_json(&serde_json::to_string_pretty(&orders).unwrap()).unwrap();
+
+            assert_eq!(orders.len(), usize::from(TOKENS));
+            for (order, listing) in orders.iter().zip(&listings) {
+                verify_order(order, chain_id).unwrap();
+                let predicate = listing.predicate().with_provider(provider.clone());
+                assert_eq!(
+                    predicate
+                        .get_asset_balance(&listing.nft_asset_id)
+                        .await
+                        .unwrap(),
+                    1
+                );
+                assert_eq!(
+                    wallet
+                        .get_asset_balance(&listing.nft_asset_id)
+                        .await
+                        .unwrap(),
+                    0
+                );
+            }
+            assert_eq!(orders[0].metadata.expires_at, Some(4_000_000_000));
+            assert_eq!(orders[1].metadata.expires_at, None);
+        }
+
+        #[tokio::test]
+        async fn funds_in_several_transactions() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let provider = wallet.provider().unwrap().clone();
+            let base_asset = *provider
+                .consensus_parameters()
+                .await
+                .unwrap()
+                .base_asset_id();
+            let collection = mint_tokens(&wallet).await;
+            let listings = terms(wallet.address().into(), base_asset, Some(collection))
+                .listings(&parse_csv(&csv_batch()).unwrap())
+                .unwrap();
+
+            // Each transaction is sent before the next one selects its coins
+            for chunk in listings.chunks(2) {
+                let mut tb = funding_transaction_builder(&wallet, chunk).await.unwrap();
+                tb.add_signer(wallet.clone()).unwrap();
+                let tx = tb.build(provider.clone()).await.unwrap();
+                provider
+                    .send_transaction_and_await_commit(tx)
+                    .await
+                    .unwrap()
+                    .check(None)
+                    .unwrap();
+            }
+
+            for listing in &listings {
+                let predicate = listing.predicate().with_provider(provider.clone());
+                assert_eq!(
+                    predicate
+                        .get_asset_balance(&listing.nft_asset_id)
+                        .await
+                        .unwrap(),
+                    1
+                );
+            }
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn both_sub_id_and_asset_id() {
+            let csv = format!(
+                "sub_id,asset_id,price\n{:#x},{:#x},100\n",
+                sub_id(0),
+                AssetId::new([3u8; 32])
+            );
+
+            assert!(matches!(parse_csv(&csv), Err(BatchError::AmbiguousNft(1))));
+        }
+
+        #[test]
+        fn malformed_rows() {
+            assert!(matches!(
+                parse_csv("sub_id,price,colour\n"),
+                Err(BatchError::Csv { row: 0, .. })
+            ));
+            assert!(matches!(
+                parse_csv(&format!("sub_id,price\n{:#x},ten\n", sub_id(0))),
+                Err(BatchError::Csv { row: 1, .. })
+            ));
+            assert!(matches!(
+                parse_csv(&format!("sub_id,price\n{:#x}\n", sub_id(0))),
+                Err(BatchError::Csv { row: 1, .. })
+            ));
+            assert!(matches!(
+                parse_csv("sub_id,price\n"),
+                Err(BatchError::Empty)
+            ));
+            // A comma within quotes is part of the cell, not a separator
+            assert!(matches!(
+                parse_csv(&format!("sub_id,price\n{:#x},\"1,000\"\n", sub_id(0))),
+                Err(BatchError::Csv { row: 1, reason }) if reason == "invalid price `1,000`"
+            ));
+            assert!(matches!(
+                parse_csv(&format!("sub_id,price\n{:#x},\"100\n", sub_id(0))),
+                Err(BatchError::Csv { row: 1, .. })
+            ));
+            assert!(matches!(
+                parse_csv(&format!("sub_id,price\n{:#x},1\"00\n", sub_id(0))),
+                Err(BatchError::Csv { row: 1, .. })
+            ));
+            assert!(matches!(
+                parse_json(r#"[{"price": 1, "colour": "red"}]"#),
+                Err(BatchError::Json(_))
+            ));
+        }
+
+        #[test]
+        fn sub_ids_without_collection() {
+            let entries = parse_csv(&csv_batch()).unwrap();
+
+            assert!(matches!(
+                terms(Address::zeroed(), AssetId::zeroed(), None).listings(&entries),
+                Err(BatchError::NoCollection)
+            ));
+        }
+
+        #[test]
+        fn same_nft_twice() {
+            let csv = format!("sub_id,price\n{0:#x},100\n{0:#x},200\n", sub_id(0));
+            let entries = parse_csv(&csv).unwrap();
+
+            assert!(matches!(
+                terms(
+                    Address::zeroed(),
+                    AssetId::zeroed(),
+                    Some(ContractId::zeroed())
+                )
+                .listings(&entries),
+                Err(BatchError::Duplicate(_))
+            ));
+        }
+    }
+}
+
+mod metadata {
+
+    use deploy_scripts::metadata::{
+        mint_token, parse_token, read_tokens, MintError, MintProgress, PROGRESS_FILE,
+    };
+    use fuels::{
+        accounts::ViewOnlyAccount,
+        prelude::{launch_provider_and_get_wallet, ContractId},
+        types::{transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity},
+    };
+    use nullstate_sdk::collection::{deploy_collection, get_asset_id, CollectionConfig, Metadata};
+    use std::{fs, path::PathBuf};
+
+    // A drop directory of its own for every test, tests run in parallel
+    fn drop_dir(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("nullstate-drop-{}-{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (name, json) in files {
+            fs::write(dir.join(name), json).unwrap();
+        }
+        dir
+    }
+
+    fn sub_id(number: u8) -> Bytes32 {
+        let mut bytes = [0u8; 32];
+        bytes[31] = number;
+        Bytes32::new(bytes)
+    }
+
+    const FILES: [(&str, &str); 3] = [
+        (
+            "1.json",
+            r#"{"name": "Null #1", "symbol": "NULL", "edition": 1, "attributes": [{"trait": "hue"}]}"#,
+        ),
+        ("0.json", r#"{"name": "Null #0", "symbol": "NULL"}"#),
+        ("README.md", "not a token"),
+    ];
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn tokens_ordered_by_sub_id() {
+            let dir = drop_dir("tokens_ordered_by_sub_id", &FILES);
+            fs::write(dir.join(PROGRESS_FILE), "{}").unwrap();
+
+            let tokens = read_tokens(&dir).unwrap();
+
+            assert_eq!(tokens.len(), 2);
+            assert_eq!(tokens[0].sub_id, sub_id(0));
+            assert_eq!(tokens[1].sub_id, sub_id(1));
+            assert_eq!(
+                tokens[1].metadata,
+                vec![
+                    (
+                        "attributes".to_string(),
+                        Metadata::String(r#"[{"trait":"hue"}]"#.to_string())
+                    ),
+                    ("edition".to_string(), Metadata::Int(1)),
+                    ("name".to_string(), Metadata::String("Null #1".to_string())),
+                    ("symbol".to_string(), Metadata::String("NULL".to_string())),
+                ]
+            );
+        }
+
+        #[test]
+        fn sub_id_key_overrides_file_name() {
+            let json = format!(r#"{{"sub_id": "{:#x}", "name": "Named"}}"#, sub_id(9));
+
+            let token = parse_token(&PathBuf::from("genesis.json"), &json).unwrap();
+
+            assert_eq!(token.sub_id, sub_id(9));
+            assert_eq!(token.metadata.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn resumes_after_partial_mint() {
+            let dir = drop_dir("resumes_after_partial_mint", &FILES);
+            let progress_path = dir.join(PROGRESS_FILE);
+            let tokens = read_tokens(&dir).unwrap();
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let recipient = Identity::Address(wallet.address().into());
+            let (contract_id, collection) =
+                deploy_collection(&wallet, &CollectionConfig::default())
+                    .await
+                    .unwrap();
+            collection
+                .methods()
+                .constructor(recipient)
+                .call()
+                .await
+                .unwrap();
+
+            // The first token is minted but its metadata is left half written
+            let mut progress = MintProgress::load(&progress_path, contract_id).unwrap();
+            let mut partial = tokens[0].clone();
+            partial.metadata.truncate(1);
+            let outcome = mint_token(
+                &collection,
+                recipient,
+                &partial,
+                &mut progress,
+                &progress_path,
+            )
+            .await
+            .unwrap();
+            assert!(outcome.minted);
+            assert_eq!(outcome.metadata_written, 1);
+
+            let mut progress = MintProgress::load(&progress_path, contract_id).unwrap();
+            let mut outcomes = vec![];
+            for token in &tokens {
+                outcomes.push(
+                    mint_token(&collection, recipient, token, &mut progress, &progress_path)
+                        .await
+                        .unwrap(),
+                );
+            }
+
+            assert!(!outcomes[0].minted);
+            assert_eq!(outcomes[0].metadata_written, 1);
+            assert!(outcomes[1].minted);
+            assert_eq!(outcomes[1].metadata_written, 4);
+            for token in &tokens {
+                let asset_id = get_asset_id(token.sub_id, contract_id);
+                assert_eq!(wallet.get_asset_balance(&asset_id).await.unwrap(), 1);
+                for (key, value) in &token.metadata {
+                    let stored = collection
+                        .methods()
+                        .metadata(asset_id, key.clone())
+                        .call()
+                        .await
+                        .unwrap()
+                        .value;
+                    assert_eq!(stored.as_ref(), Some(value));
+                }
+            }
+
+            // Everything is recorded, running again sends nothing
+            let mut progress = MintProgress::load(&progress_path, contract_id).unwrap();
+            for token in &tokens {
+                let outcome =
+                    mint_token(&collection, recipient, token, &mut progress, &progress_path)
+                        .await
+                        .unwrap();
+                assert!(!outcome.minted);
+                assert_eq!(outcome.metadata_written, 0);
+            }
+        }
+
+        #[tokio::test]
+        async fn skips_what_is_on_chain_but_not_recorded() {
+            let dir = drop_dir("skips_what_is_on_chain_but_not_recorded", &FILES);
+            let progress_path = dir.join(PROGRESS_FILE);
+            let token = read_tokens(&dir).unwrap().remove(1);
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let recipient = Identity::Address(wallet.address().into());
+            let (contract_id, collection) =
+                deploy_collection(&wallet, &CollectionConfig::default())
+                    .await
+                    .unwrap();
+            collection
+                .methods()
+                .constructor(recipient)
+                .call()
+                .await
+                .unwrap();
+
+            // The mint and the first entry were committed but the progress file never saved
+            let asset_id = get_asset_id(token.sub_id, contract_id);
+            collection
+                .methods()
+                .mint(recipient, Bits256(*token.sub_id), 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .unwrap();
+            let (key, value) = token.metadata[0].clone();
+            collection
+                .methods()
+                .set_metadata(asset_id, key, value)
+                .call()
+                .await
+                .unwrap();
+
+            let mut progress = MintProgress::load(&progress_path, contract_id).unwrap();
+            let outcome = mint_token(
+                &collection,
+                recipient,
+                &token,
+                &mut progress,
+                &progress_path,
+            )
+            .await
+            .unwrap();
+
+            assert!(!outcome.minted);
+            assert_eq!(outcome.metadata_written, token.metadata.len() - 1);
+            assert_eq!(wallet.get_asset_balance(&asset_id).await.unwrap(), 1);
+            let recorded = MintProgress::load(&progress_path, contract_id).unwrap();
+            let recorded = recorded.token(token.sub_id).unwrap();
+            assert!(recorded.minted);
+            assert_eq!(recorded.mint_tx, None);
+            assert_eq!(recorded.metadata.len(), token.metadata.len());
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn no_sub_id() {
+            assert!(matches!(
+                parse_token(&PathBuf::from("genesis.json"), r#"{"name": "Unnamed"}"#),
+                Err(MintError::InvalidSubId(_))
+            ));
+            assert!(matches!(
+                parse_token(&PathBuf::from("0.json"), r#"{"sub_id": 7}"#),
+                Err(MintError::InvalidSubId(_))
+            ));
+        }
+
+        #[test]
+        fn same_token_twice() {
+            let json = format!(r#"{{"sub_id": "{:#x}"}}"#, sub_id(0));
+            let dir = drop_dir(
+                "same_token_twice",
+                &[("0.json", "{}"), ("zero.json", json.as_str())],
+            );
+
+            assert!(matches!(
+                read_tokens(&dir),
+                Err(MintError::DuplicateSubId(_))
+            ));
+        }
+
+        #[test]
+        fn empty_directory() {
+            let dir = drop_dir("empty_directory", &[("README.md", "")]);
+
+            assert!(matches!(read_tokens(&dir), Err(MintError::Empty(_))));
+        }
+
+        #[test]
+        fn progress_of_other_contract() {
+            let dir = drop_dir("progress_of_other_contract", &[]);
+            let path = dir.join(PROGRESS_FILE);
+            MintProgress::load(&path, ContractId::new([1u8; 32]))
+                .unwrap()
+                .save(&path)
+                .unwrap();
+
+            assert!(matches!(
+                MintProgress::load(&path, ContractId::new([2u8; 32])),
+                Err(MintError::OtherContract { .. })
+            ));
+        }
+    }
+}
+
+mod portfolio {
+
+    use deploy_scripts::{
+        manifest::{
+            CollectionDeployment, Manifest, MintRecord, PredicateConfigurables,
+            PredicateDeployment, MANIFEST_VERSION,
+        },
+        portfolio::Portfolio,
+    };
+    use fuels::{
+        accounts::ViewOnlyAccount,
+        prelude::{
+            launch_provider_and_get_wallet, Account, Address, AssetId, Bech32Address, ContractId,
+            TxPolicies,
+        },
+        types::{transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity},
+    };
+    use nullstate_indexer::store::{IndexedListing, ListingQuery, ListingStatus, Store};
+    use nullstate_sdk::{
+        collection::{deploy_collection, get_asset_id, CollectionConfig},
+        fee::FeePolicy,
+        listing::ListingParams,
+        offer::{CollectionOfferParams, Offer, OfferParams},
+        order::{Order, OrderMetadata},
+    };
+    use std::collections::HashMap;
+
+    const ASK_ASSET: AssetId = AssetId::new([8u8; 32]);
+
+    fn manifest(contract_id: ContractId, sub_ids: &[Bytes32]) -> Manifest {
+        Manifest {
+            version: MANIFEST_VERSION,
+            network: "local".to_string(),
+            chain_id: 0,
+            collection: CollectionDeployment {
+                contract_id,
+                owner: Address::zeroed(),
+                salt: Bytes32::zeroed(),
+                deploy_tx: None,
+                constructor_tx: None,
+            },
+            predicate: PredicateDeployment {
+                bytecode_hash: Bytes32::zeroed(),
+                configurables: PredicateConfigurables::new(None, FeePolicy::Fixed(0)),
+            },
+            mints: sub_ids
+                .iter()
+                .map(|sub_id| MintRecord {
+                    sub_id: *sub_id,
+                    asset_id: get_asset_id(*sub_id, contract_id),
+                    recipient: Address::zeroed(),
+                    amount: 1,
+                    tx_id: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn order(nft_asset_id: AssetId, receiver: Address) -> Order {
+        let listing = ListingParams::new(nft_asset_id, receiver).with_price(ASK_ASSET, 40);
+
+        Order::from_listing(
+            0,
+            listing,
+            OrderMetadata {
+                created_at: 0,
+                block_height: 0,
+                expires_at: None,
+            },
+        )
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn sorts_nfts_listings_and_balances() {
+            let address = Address::new([1u8; 32]);
+            let base_asset = AssetId::zeroed();
+            let contract_id = ContractId::new([2u8; 32]);
+            let minted = get_asset_id(Bytes32::new([3u8; 32]), contract_id);
+            let foreign = AssetId::new([4u8; 32]);
+            let fungible = AssetId::new([5u8; 32]);
+            let held = HashMap::from([
+                (base_asset, 1),
+                (ASK_ASSET, 1),
+                (minted, 1),
+                (foreign, 1),
+                (fungible, 1_000),
+            ]);
+            let listings = [
+                IndexedListing {
+                    order: order(AssetId::new([6u8; 32]), address),
+                    status: ListingStatus::Live,
+                    funded_tx: None,
+                    closed_tx: None,
+                    updated_height: None,
+                },
+                IndexedListing {
+                    order: order(AssetId::new([7u8; 32]), Address::new([9u8; 32])),
+                    status: ListingStatus::Live,
+                    funded_tx: None,
+                    closed_tx: None,
+                    updated_height: None,
+                },
+            ];
+            let manifest = manifest(contract_id, &[Bytes32::new([3u8; 32])]);
+            let bidder = Address::new([9u8; 32]);
+            let made = Offer::Nft(
+                OfferParams::new(AssetId::new([6u8; 32]), address).with_payment(ASK_ASSET, 30),
+            );
+            let made_and_taken = Offer::Nft(
+                OfferParams::new(AssetId::new([7u8; 32]), address).with_payment(ASK_ASSET, 30),
+            );
+            let for_foreign =
+                Offer::Nft(OfferParams::new(foreign, bidder).with_payment(ASK_ASSET, 20));
+            let for_collection = Offer::Collection(
+                CollectionOfferParams::new(contract_id, bidder).with_payment(ASK_ASSET, 10),
+            );
+            let for_someone_else = Offer::Nft(
+                OfferParams::new(AssetId::new([7u8; 32]), bidder).with_payment(ASK_ASSET, 20),
+            );
+            let offers = [
+                (made.clone(), 30),
+                (made_and_taken, 0),
+                (for_foreign.clone(), 20),
+                (for_collection.clone(), 10),
+                (for_someone_else, 20),
+            ];
+
+            let portfolio = Portfolio::new(
+                address,
+                &held,
+                base_asset,
+                Some(&manifest),
+                &listings,
+                &offers,
+            );
+
+            // `foreign` is held exactly once, it is listed without a collection
+            assert_eq!(portfolio.nfts.len(), 2);
+            let resolved = portfolio
+                .nfts
+                .iter()
+                .find(|nft| nft.asset_id == minted)
+                .unwrap();
+            assert_eq!(resolved.contract_id, Some(contract_id));
+            assert_eq!(resolved.sub_id, Some(Bytes32::new([3u8; 32])));
+            let unresolved = portfolio
+                .nfts
+                .iter()
+                .find(|nft| nft.asset_id == foreign)
+                .unwrap();
+            assert_eq!(unresolved.contract_id, None);
+            assert_eq!(unresolved.sub_id, None);
+
+            let offers_made: Vec<_> = portfolio
+                .offers_made
+                .iter()
+                .map(|open| (open.offer.clone(), open.locked))
+                .collect();
+            assert_eq!(offers_made, vec![(made.clone(), 30)]);
+            assert_eq!(
+                portfolio.offers_made[0].predicate_address,
+                made.predicate_address()
+            );
+            let offers_received: Vec<_> = portfolio
+                .offers_received
+                .iter()
+                .map(|open| (open.offer.clone(), open.locked))
+                .collect();
+            assert_eq!(
+                offers_received,
+                vec![(for_foreign, 20), (for_collection, 10)]
+            );
+
+            assert_eq!(portfolio.listings.len(), 1);
+            assert_eq!(portfolio.listings[0].status, "live");
+            assert_eq!(portfolio.listings[0].price, 40);
+
+            let balances: Vec<_> = portfolio
+                .balances
+                .iter()
+                .map(|balance| (balance.asset_id, balance.amount))
+                .collect();
+            assert_eq!(balances, vec![(base_asset, 1), (ASK_ASSET, 1)]);
+
+            let json: serde_json::Value = serde_json::to_value(&portfolio).unwrap();
+            assert_eq!(json["nfts"].as_array().unwrap().len(), 2);
+            assert_eq!(json["listings"][0]["status"], "live");
+            assert_eq!(json["offers_made"][0]["offer"]["kind"], "nft");
+            assert_eq!(json["offers_received"][1]["offer"]["kind"], "collection");
+            assert!(portfolio.to_string().contains("collection"));
+        }
+
+        #[tokio::test]
+        async fn reads_holdings_from_the_node() {
+            let wallet = launch_provider_and_get_wallet().await.unwrap();
+            let provider = wallet.provider().unwrap().clone();
+            let address = Address::from(wallet.address());
+            let (contract_id, collection) =
+                deploy_collection(&wallet, &CollectionConfig::default())
+                    .await
+                    .unwrap();
+            collection
+                .methods()
+                .constructor(Identity::Address(address))
+                .call()
+                .await
+                .unwrap();
+            let sub_id = Bytes32::new([3u8; 32]);
+            collection
+                .methods()
+                .mint(Identity::Address(address), Bits256(*sub_id), 1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await
+                .unwrap();
+
+            let store = Store::in_memory().unwrap();
+            store
+                .insert_order(&order(AssetId::new([6u8; 32]), address))
+                .unwrap();
+            let listings = store
+                .listings(&ListingQuery {
+                    receiver: Some(address),
+                    ..ListingQuery::default()
+                })
+                .unwrap();
+            let manifest = manifest(contract_id, &[sub_id]);
+
+            // A bid on the collection, funded, and one never funded
+            let base_asset = *provider
+                .consensus_parameters()
+                .await
+                .unwrap()
+                .base_asset_id();
+            let bidder = Address::new([9u8; 32]);
+            let funded = Offer::Collection(
+                CollectionOfferParams::new(contract_id, bidder).with_payment(base_asset, 100),
+            );
+            let unfunded = Offer::Collection(
+                CollectionOfferParams::new(contract_id, bidder).with_payment(base_asset, 200),
+            );
+            wallet
+                .transfer(
+                    &Bech32Address::from(funded.predicate_address()),
+                    100,
+                    base_asset,
+                    TxPolicies::default(),
+                )
+                .await
+                .unwrap();
+            store.insert_offer(&funded).unwrap();
+            store.insert_offer(&unfunded).unwrap();
+            let offers = store.offers().unwrap();
+
+            let portfolio =
+                Portfolio::fetch(&provider, address, Some(&manifest), &listings, &offers)
+                    .await
+                    .unwrap();
+
+            let asset_id = get_asset_id(sub_id, contract_id);
+            assert_eq!(portfolio.nfts.len(), 1);
+            assert_eq!(portfolio.nfts[0].asset_id, asset_id);
+            assert_eq!(portfolio.nfts[0].sub_id, Some(sub_id));
+            assert_eq!(portfolio.listings.len(), 1);
+            assert_eq!(portfolio.listings[0].status, "pending");
+            assert!(portfolio.offers_made.is_empty());
+            assert_eq!(portfolio.offers_received.len(), 1);
+            assert_eq!(portfolio.offers_received[0].offer, funded);
+            assert_eq!(portfolio.offers_received[0].locked, 100);
+            let base_balance = portfolio
+                .balances
+                .iter()
+                .find(|balance| balance.asset_id == base_asset)
+                .unwrap();
+            assert_eq!(
+                base_balance.amount,
+                u128::from(wallet.get_asset_balance(&base_asset).await.unwrap())
+            );
+        }
+    }
+}
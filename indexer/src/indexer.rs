@@ -0,0 +1,243 @@
+//! Follows the chain block by block and tracks the listings of the known orders
+//!
+//! A listing goes live when a transaction outputs its NFT to the predicate address of the order.
+//! It closes when a transaction spends that predicate coin: the predicate only lets the receiver
+//! spend it next to a coin of their own, so a spend with an input owned by the receiver is a
+//! cancellation and any other spend is a fill
+
+use crate::store::{ListingStatus, Store, StoreError};
+use fuels::{
+    prelude::Provider,
+    tx::{Input, Output},
+    types::{
+        bech32::Bech32Address,
+        errors::Error as ProviderError,
+        transaction::{Transaction, TransactionType},
+        tx_status::TxStatus,
+        AssetId, Bytes32,
+    },
+};
+use nullstate_sdk::{
+    order::Order,
+    predicate::{verify_order, VerifyError},
+};
+use std::{fmt, time::Duration};
+
+/// Why the indexer stopped
+#[derive(Debug)]
+pub enum IndexerError {
+    Store(StoreError),
+    Provider(ProviderError),
+    /// An order was refused before being stored
+    Verify(VerifyError),
+    /// The node does not have a block below its latest height
+    MissingBlock(u32),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "{e}"),
+            Self::Provider(e) => write!(f, "provider error: {e}"),
+            Self::Verify(e) => write!(f, "order rejected: {e}"),
+            Self::MissingBlock(height) => write!(f, "node has no block at height {height}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<StoreError> for IndexerError {
+    fn from(e: StoreError) -> Self {
+        Self::Store(e)
+    }
+}
+
+impl From<ProviderError> for IndexerError {
+    fn from(e: ProviderError) -> Self {
+        Self::Provider(e)
+    }
+}
+
+pub struct Indexer {
+    provider: Provider,
+    store: Store,
+    chain_id: u64,
+    base_asset_id: AssetId,
+    start_height: u32,
+}
+
+impl Indexer {
+    /// Creates an indexer of the chain `provider` is connected to, starting at the genesis block
+    pub async fn new(provider: Provider, store: Store) -> Result<Self, IndexerError> {
+        let consensus_parameters = provider.consensus_parameters().await?;
+
+        Ok(Self {
+            chain_id: u64::from(consensus_parameters.chain_id()),
+            base_asset_id: *consensus_parameters.base_asset_id(),
+            provider,
+            store,
+            start_height: 0,
+        })
+    }
+
+    /// Starts the first sync at `height` instead of the genesis block
+    ///
+    /// Orders funded before `height` are never seen live, so it should not be later than the
+    /// creation of the oldest order
+    pub fn with_start_height(mut self, height: u32) -> Self {
+        self.start_height = height;
+        self
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Verifies `order` against the known predicate releases and starts tracking its listing
+    ///
+    /// Orders are usually added after their NFT was sent to the predicate, possibly in a block
+    /// already synced, so a new listing whose predicate holds the NFT goes live right away.
+    /// Returns false when the order was already tracked
+    pub async fn add_order(&self, order: &Order) -> Result<bool, IndexerError> {
+        verify_order(order, self.chain_id).map_err(IndexerError::Verify)?;
+
+        if !self.store.insert_order(order)? {
+            return Ok(false);
+        }
+
+        let coins = self
+            .provider
+            .get_coins(
+                &Bech32Address::from(order.predicate_address),
+                order.listing.nft_asset_id,
+            )
+            .await?;
+        if let Some(coin) = coins.first() {
+            self.store.mark_funded(
+                order.predicate_address,
+                *coin.utxo_id.tx_id(),
+                coin.block_created,
+            )?;
+        }
+
+        Ok(true)
+    }
+
+    /// Indexes every block since the last sync, returns the height of the latest block indexed
+    pub async fn sync(&self) -> Result<u32, IndexerError> {
+        let latest_height = self.provider.latest_block_height().await?;
+        let mut height = match self.store.last_height()? {
+            Some(last_height) => last_height + 1,
+            None => self.start_height,
+        };
+
+        while height <= latest_height {
+            self.index_block(height).await?;
+            height += 1;
+        }
+
+        Ok(height.saturating_sub(1))
+    }
+
+    /// Syncs every `poll_interval`, until an error occurs
+    pub async fn run(&self, poll_interval: Duration) -> Result<(), IndexerError> {
+        loop {
+            self.sync().await?;
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Indexes the block at `height` and records it as the last one synced
+    ///
+    /// Its transactions are fetched first and then applied together with the new height in one
+    /// store transaction, so a failure never leaves the block half indexed
+    async fn index_block(&self, height: u32) -> Result<(), IndexerError> {
+        let block = self
+            .provider
+            .block_by_height(height.into())
+            .await?
+            .ok_or(IndexerError::MissingBlock(height))?;
+
+        let mut transactions = vec![];
+        for tx_id in block.transactions {
+            let Some(response) = self.provider.get_transaction_by_id(&tx_id).await? else {
+                continue;
+            };
+
+            // Only a successful transaction moves the NFT the way its outputs describe
+            if !matches!(response.status, TxStatus::Success { .. }) {
+                continue;
+            }
+
+            if let TransactionType::Script(tx) = response.transaction {
+                transactions.push((tx_id, tx));
+            }
+        }
+
+        self.store.in_transaction(|store| {
+            for (tx_id, tx) in &transactions {
+                self.index_transaction(*tx_id, height, tx.inputs(), tx.outputs())?;
+            }
+            store.set_last_height(height)?;
+
+            Ok(())
+        })
+    }
+
+    /// Applies the spends of a transaction before its outputs, so a listing closed and funded
+    /// again within the same transaction ends up live
+    fn index_transaction(
+        &self,
+        tx_id: Bytes32,
+        height: u32,
+        inputs: &[Input],
+        outputs: &[Output],
+    ) -> Result<(), IndexerError> {
+        for input in inputs {
+            let (Some(owner), Some(asset_id)) =
+                (input.input_owner(), input.asset_id(&self.base_asset_id))
+            else {
+                continue;
+            };
+
+            let listings = self.store.listings_at(*owner)?;
+            let Some(listing) = listings.first().map(|listed| &listed.order.listing) else {
+                continue;
+            };
+            if *asset_id != listing.nft_asset_id {
+                continue;
+            }
+
+            let cancelled = inputs
+                .iter()
+                .any(|input| input.input_owner() == Some(&listing.receiver));
+            let status = if cancelled {
+                ListingStatus::Cancelled
+            } else {
+                ListingStatus::Filled
+            };
+            self.store.mark_closed(*owner, status, tx_id, height)?;
+        }
+
+        for output in outputs {
+            let Output::Coin { to, asset_id, .. } = output else {
+                continue;
+            };
+
+            let listings = self.store.listings_at(*to)?;
+            if listings
+                .first()
+                .is_some_and(|listed| listed.order.listing.nft_asset_id == *asset_id)
+            {
+                self.store.mark_funded(*to, tx_id, height)?;
+            }
+        }
+
+        Ok(())
+    }
+}
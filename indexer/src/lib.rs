@@ -0,0 +1,4 @@
+//! Local order book of fixed price listings, kept in SQLite by following the chain
+
+pub mod indexer;
+pub mod store;
@@ -0,0 +1,150 @@
+use clap::{Parser, Subcommand};
+use fuels::{
+    prelude::Provider,
+    types::{Address, AssetId, Bytes32},
+};
+use nullstate_indexer::{
+    indexer::Indexer,
+    store::{IndexedListing, ListingQuery, ListingStatus, Store},
+};
+use nullstate_sdk::{offer::Offer, order::Order, serde_hex};
+use serde_json::{json, Value};
+use std::{error::Error, fs, path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Indexes nullstate fixed price listings into a local SQLite order book")]
+struct Cli {
+    /// The SQLite database holding the order book
+    #[arg(long, default_value = "orderbook.sqlite")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Verifies orders and adds their listings to the order book
+    AddOrders {
+        #[arg(long, default_value = "127.0.0.1:4000")]
+        node_url: String,
+        /// JSON files holding an order or an array of orders
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Adds buyer offers to the order book, `portfolio` reads whether they are open from the node
+    AddOffers {
+        /// JSON files holding an offer or an array of offers
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Follows the chain and updates the status of every listing
+    Run {
+        #[arg(long, default_value = "127.0.0.1:4000")]
+        node_url: String,
+        /// The block to start at when the database was never synced
+        #[arg(long, default_value_t = 0)]
+        from_height: u32,
+        /// Seconds to wait between syncs
+        #[arg(long, default_value_t = 5)]
+        poll_interval: u64,
+        /// Syncs up to the latest block and exits
+        #[arg(long)]
+        once: bool,
+    },
+    /// Prints the listings matching every filter as JSON
+    Query {
+        /// One of `pending`, `live`, `filled` or `cancelled`
+        #[arg(long)]
+        status: Option<ListingStatus>,
+        #[arg(long, value_parser = parse_id::<Address>)]
+        receiver: Option<Address>,
+        #[arg(long, value_parser = parse_id::<AssetId>)]
+        nft: Option<AssetId>,
+    },
+}
+
+fn parse_id<T: From<[u8; 32]>>(hex: &str) -> Result<T, String> {
+    serde_hex::decode(hex)
+        .map(T::from)
+        .ok_or_else(|| format!("`{hex}` is not a 32 byte hex string"))
+}
+
+fn listing_json(listed: &IndexedListing) -> Value {
+    let tx = |tx_id: Option<Bytes32>| tx_id.map(|tx_id| serde_hex::encode(&tx_id));
+
+    json!({
+        "order_hash": serde_hex::encode(&listed.order.hash()),
+        "status": listed.status.as_str(),
+        "funded_tx": tx(listed.funded_tx),
+        "closed_tx": tx(listed.closed_tx),
+        "updated_height": listed.updated_height,
+        "order": listed.order,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::AddOrders { node_url, files } => {
+            let indexer = Indexer::new(Provider::connect(node_url).await?, store).await?;
+
+            for file in files {
+                for order in Order::many_from_json(&fs::read_to_string(&file)?)? {
+                    let added = indexer.add_order(&order).await?;
+                    println!(
+                        "{} {}",
+                        serde_hex::encode(&order.hash()),
+                        if added { "added" } else { "already known" }
+                    );
+                }
+            }
+        }
+        Command::AddOffers { files } => {
+            for file in files {
+                for offer in Offer::many_from_json(&fs::read_to_string(&file)?)? {
+                    let added = store.insert_offer(&offer)?;
+                    println!(
+                        "{} {}",
+                        serde_hex::encode(&offer.predicate_address()),
+                        if added { "added" } else { "already known" }
+                    );
+                }
+            }
+        }
+        Command::Run {
+            node_url,
+            from_height,
+            poll_interval,
+            once,
+        } => {
+            let indexer = Indexer::new(Provider::connect(node_url).await?, store)
+                .await?
+                .with_start_height(from_height);
+
+            if once {
+                println!("synced up to block {}", indexer.sync().await?);
+            } else {
+                indexer.run(Duration::from_secs(poll_interval)).await?;
+            }
+        }
+        Command::Query {
+            status,
+            receiver,
+            nft,
+        } => {
+            let listings = store.listings(&ListingQuery {
+                status,
+                receiver,
+                nft_asset_id: nft,
+            })?;
+
+            let listings: Vec<Value> = listings.iter().map(listing_json).collect();
+            println!("{}", serde_json::to_string_pretty(&listings)?);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,396 @@
+//! SQLite storage of the known orders and the on-chain status of their listings
+//!
+//! Orders are stored in their binary encoding next to the columns they are queried by. A listing
+//! is `pending` until its NFT is sent to the predicate, `live` while the predicate holds it and
+//! `filled` or `cancelled` once the predicate coin is spent
+//!
+//! Buyer offers are stored as JSON by predicate address. Their status is not followed, whether
+//! an offer is still open is read from the balance of its predicate
+
+use fuels::types::{Address, AssetId, Bytes32};
+use nullstate_sdk::{
+    offer::Offer,
+    order::{Order, OrderError},
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::{fmt, path::Path, str::FromStr};
+
+/// Where a listing is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingStatus {
+    /// The order is known but its NFT has not been sent to the predicate
+    Pending,
+    /// The predicate holds the NFT
+    Live,
+    /// A buyer took the NFT
+    Filled,
+    /// The seller took the NFT back
+    Cancelled,
+}
+
+impl ListingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Live => "live",
+            Self::Filled => "filled",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for ListingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ListingStatus {
+    type Err = StoreError;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "pending" => Ok(Self::Pending),
+            "live" => Ok(Self::Live),
+            "filled" => Ok(Self::Filled),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(StoreError::UnknownStatus(status.to_string())),
+        }
+    }
+}
+
+/// An order together with what the indexer saw happen to its listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedListing {
+    pub order: Order,
+    pub status: ListingStatus,
+    /// The transaction that last sent the NFT to the predicate
+    pub funded_tx: Option<Bytes32>,
+    /// The transaction that spent the predicate coin
+    pub closed_tx: Option<Bytes32>,
+    /// The block of the last status change
+    pub updated_height: Option<u32>,
+}
+
+/// Filters of `Store::listings`, every field left to `None` matches all listings
+#[derive(Debug, Clone, Default)]
+pub struct ListingQuery {
+    pub status: Option<ListingStatus>,
+    pub receiver: Option<Address>,
+    pub nft_asset_id: Option<AssetId>,
+}
+
+/// Why the store could not be read or written
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    /// A stored order no longer decodes
+    Order(OrderError),
+    /// A stored offer no longer decodes
+    Offer(serde_json::Error),
+    /// A stored status is not one of `ListingStatus`
+    UnknownStatus(String),
+    /// A stored id is not 32 bytes long
+    InvalidId(usize),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sqlite(e) => write!(f, "sqlite error: {e}"),
+            Self::Order(e) => write!(f, "stored order is invalid: {e}"),
+            Self::Offer(e) => write!(f, "stored offer is invalid: {e}"),
+            Self::UnknownStatus(status) => write!(f, "unknown listing status `{status}`"),
+            Self::InvalidId(len) => write!(f, "stored id is {len} bytes long, expected 32"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        Self::Sqlite(e)
+    }
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS orders (
+        order_hash BLOB PRIMARY KEY,
+        predicate_address BLOB NOT NULL,
+        receiver BLOB NOT NULL,
+        nft_asset_id BLOB NOT NULL,
+        encoded BLOB NOT NULL,
+        status TEXT NOT NULL,
+        funded_tx BLOB,
+        closed_tx BLOB,
+        updated_height INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS orders_by_predicate ON orders (predicate_address);
+    CREATE INDEX IF NOT EXISTS orders_by_receiver ON orders (receiver);
+    CREATE INDEX IF NOT EXISTS orders_by_nft ON orders (nft_asset_id);
+    CREATE TABLE IF NOT EXISTS offers (
+        predicate_address BLOB PRIMARY KEY,
+        receiver BLOB NOT NULL,
+        encoded TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS sync_state (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        last_height INTEGER NOT NULL
+    );
+";
+
+const LISTING_COLUMNS: &str = "encoded, status, funded_tx, closed_tx, updated_height";
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Opens the database at `path`, creating it and its tables when missing
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A database that lives as long as the store, for tests and one-off runs
+    pub fn in_memory() -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Adds `order` as a pending listing, returns false when it is already stored
+    pub fn insert_order(&self, order: &Order) -> Result<bool, StoreError> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO orders
+                (order_hash, predicate_address, receiver, nft_asset_id, encoded, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                order.hash().as_slice(),
+                order.predicate_address.as_slice(),
+                order.listing.receiver.as_slice(),
+                order.listing.nft_asset_id.as_slice(),
+                order.to_bytes(),
+                ListingStatus::Pending.as_str(),
+            ],
+        )?;
+
+        Ok(inserted == 1)
+    }
+
+    /// The listing of the order with `order_hash`
+    pub fn listing(&self, order_hash: Bytes32) -> Result<Option<IndexedListing>, StoreError> {
+        self.conn
+            .query_row(
+                &format!("SELECT {LISTING_COLUMNS} FROM orders WHERE order_hash = ?1"),
+                [order_hash.as_slice()],
+                read_row,
+            )
+            .optional()?
+            .map(IndexedListing::try_from)
+            .transpose()
+    }
+
+    /// Every listing locking its NFT at `predicate_address`
+    ///
+    /// Orders for the same listing terms share a predicate, they differ only in their metadata
+    pub fn listings_at(
+        &self,
+        predicate_address: Address,
+    ) -> Result<Vec<IndexedListing>, StoreError> {
+        let mut statement = self.conn.prepare_cached(&format!(
+            "SELECT {LISTING_COLUMNS} FROM orders WHERE predicate_address = ?1 ORDER BY rowid"
+        ))?;
+        let rows = statement.query_map([predicate_address.as_slice()], read_row)?;
+
+        rows.map(|row| IndexedListing::try_from(row?)).collect()
+    }
+
+    /// The listings matching every filter of `query`, in the order they were added
+    pub fn listings(&self, query: &ListingQuery) -> Result<Vec<IndexedListing>, StoreError> {
+        let mut statement = self.conn.prepare_cached(&format!(
+            "SELECT {LISTING_COLUMNS} FROM orders
+             WHERE (?1 IS NULL OR status = ?1)
+               AND (?2 IS NULL OR receiver = ?2)
+               AND (?3 IS NULL OR nft_asset_id = ?3)
+             ORDER BY rowid"
+        ))?;
+        let rows = statement.query_map(
+            params![
+                query.status.map(|status| status.as_str()),
+                query.receiver.as_ref().map(|receiver| receiver.as_slice()),
+                query
+                    .nft_asset_id
+                    .as_ref()
+                    .map(|asset_id| asset_id.as_slice()),
+            ],
+            read_row,
+        )?;
+
+        rows.map(|row| IndexedListing::try_from(row?)).collect()
+    }
+
+    /// Marks the listings at `predicate_address` live, unless they already are
+    ///
+    /// A filled or cancelled listing becomes live again when its NFT is sent back to the predicate
+    pub fn mark_funded(
+        &self,
+        predicate_address: Address,
+        tx_id: Bytes32,
+        height: u32,
+    ) -> Result<usize, StoreError> {
+        Ok(self.conn.execute(
+            "UPDATE orders SET status = ?1, funded_tx = ?2, closed_tx = NULL, updated_height = ?3
+             WHERE predicate_address = ?4 AND status != ?1",
+            params![
+                ListingStatus::Live.as_str(),
+                tx_id.as_slice(),
+                height,
+                predicate_address.as_slice(),
+            ],
+        )?)
+    }
+
+    /// Marks the live listings at `predicate_address` filled or cancelled
+    pub fn mark_closed(
+        &self,
+        predicate_address: Address,
+        status: ListingStatus,
+        tx_id: Bytes32,
+        height: u32,
+    ) -> Result<usize, StoreError> {
+        debug_assert!(matches!(
+            status,
+            ListingStatus::Filled | ListingStatus::Cancelled
+        ));
+
+        Ok(self.conn.execute(
+            "UPDATE orders SET status = ?1, closed_tx = ?2, updated_height = ?3
+             WHERE predicate_address = ?4 AND status = ?5",
+            params![
+                status.as_str(),
+                tx_id.as_slice(),
+                height,
+                predicate_address.as_slice(),
+                ListingStatus::Live.as_str(),
+            ],
+        )?)
+    }
+
+    /// Adds `offer`, returns false when it is already stored
+    pub fn insert_offer(&self, offer: &Offer) -> Result<bool, StoreError> {
+        let encoded = serde_json::to_string(offer).expect("offers always serialize to JSON");
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO offers (predicate_address, receiver, encoded)
+             VALUES (?1, ?2, ?3)",
+            params![
+                offer.predicate_address().as_slice(),
+                offer.receiver().as_slice(),
+                encoded,
+            ],
+        )?;
+
+        Ok(inserted == 1)
+    }
+
+    /// Every stored offer, in the order they were added
+    pub fn offers(&self) -> Result<Vec<Offer>, StoreError> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT encoded FROM offers ORDER BY rowid")?;
+        let encoded = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        encoded
+            .iter()
+            .map(|json| serde_json::from_str(json).map_err(StoreError::Offer))
+            .collect()
+    }
+
+    /// Runs `f` in a single transaction, committed only when it succeeds
+    ///
+    /// Every write `f` makes through the store is rolled back when it fails, so a block is
+    /// either indexed along with its height or not at all
+    pub fn in_transaction<T, E: From<StoreError>>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        // The writes go through `self.conn`, which the transaction only borrows
+        let transaction = self
+            .conn
+            .unchecked_transaction()
+            .map_err(StoreError::from)?;
+        let value = f(self)?;
+        transaction.commit().map_err(StoreError::from)?;
+
+        Ok(value)
+    }
+
+    /// The last block the indexer processed, `None` before the first sync
+    pub fn last_height(&self) -> Result<Option<u32>, StoreError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT last_height FROM sync_state WHERE id = 0",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_last_height(&self, height: u32) -> Result<(), StoreError> {
+        self.conn.execute(
+            "INSERT INTO sync_state (id, last_height) VALUES (0, ?1)
+             ON CONFLICT (id) DO UPDATE SET last_height = excluded.last_height",
+            [height],
+        )?;
+        Ok(())
+    }
+}
+
+/// The raw columns of a listing, decoded outside of rusqlite to keep its own errors
+struct ListingRow {
+    encoded: Vec<u8>,
+    status: String,
+    funded_tx: Option<Vec<u8>>,
+    closed_tx: Option<Vec<u8>>,
+    updated_height: Option<u32>,
+}
+
+fn read_row(row: &Row<'_>) -> rusqlite::Result<ListingRow> {
+    Ok(ListingRow {
+        encoded: row.get(0)?,
+        status: row.get(1)?,
+        funded_tx: row.get(2)?,
+        closed_tx: row.get(3)?,
+        updated_height: row.get(4)?,
+    })
+}
+
+fn read_id(bytes: Option<Vec<u8>>) -> Result<Option<Bytes32>, StoreError> {
+    bytes
+        .map(|bytes| {
+            <[u8; 32]>::try_from(bytes.as_slice())
+                .map(Bytes32::from)
+                .map_err(|_| StoreError::InvalidId(bytes.len()))
+        })
+        .transpose()
+}
+
+impl TryFrom<ListingRow> for IndexedListing {
+    type Error = StoreError;
+
+    fn try_from(row: ListingRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            order: Order::from_bytes(&row.encoded).map_err(StoreError::Order)?,
+            status: row.status.parse()?,
+            funded_tx: read_id(row.funded_tx)?,
+            closed_tx: read_id(row.closed_tx)?,
+            updated_height: row.updated_height,
+        })
+    }
+}
@@ -0,0 +1,538 @@
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{
+        launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, ContractId,
+        TxPolicies, WalletUnlocked,
+    },
+    test_helpers::WalletsConfig,
+    types::{
+        bech32::Bech32Address,
+        input::Input,
+        output::Output,
+        transaction::Transaction,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        Bytes32,
+    },
+};
+use nullstate_indexer::{
+    indexer::{Indexer, IndexerError},
+    store::{ListingQuery, ListingStatus, Store, StoreError},
+};
+use nullstate_sdk::{
+    listing::ListingParams,
+    offer::{CollectionOfferParams, Offer, OfferParams},
+    order::{Order, OrderMetadata},
+};
+
+const BASE_ASSET: AssetId = AssetId::zeroed();
+const ASK_ASSET: AssetId = AssetId::new([1u8; 32]);
+const NFT_ASSET: AssetId = AssetId::new([2u8; 32]);
+const OTHER_NFT_ASSET: AssetId = AssetId::new([3u8; 32]);
+const PRICE: u64 = 1_000;
+const FEE: u64 = 25;
+const TREASURY: Address = Address::new([4u8; 32]);
+
+// Create wallet config for the seller and the buyer, each holding a single coin per asset
+fn configure_wallets() -> WalletsConfig {
+    let coin = |id, coin_amount| AssetConfig {
+        id,
+        num_coins: 1,
+        coin_amount,
+    };
+
+    WalletsConfig::new_multiple_assets(
+        2,
+        vec![
+            coin(BASE_ASSET, 1_000_000_000),
+            coin(ASK_ASSET, 1_000_000_000),
+            coin(NFT_ASSET, 1),
+            coin(OTHER_NFT_ASSET, 1),
+        ],
+    )
+}
+
+// The order of a listing of `nft_asset_id` by `seller` for `PRICE` plus a fixed fee
+fn order(indexer: &Indexer, seller: &WalletUnlocked, nft_asset_id: AssetId) -> Order {
+    let mut listing = ListingParams::new(nft_asset_id, seller.address().into());
+    listing.ask_asset = ASK_ASSET;
+    listing.ask_amount = PRICE;
+    listing.fee_asset = ASK_ASSET;
+    listing.treasury = TREASURY;
+    listing.fee_amount = FEE;
+
+    Order::from_listing(
+        indexer.chain_id(),
+        listing,
+        OrderMetadata {
+            created_at: 0,
+            block_height: 0,
+            expires_at: None,
+        },
+    )
+}
+
+// Launches a node and an indexer of it over an in-memory store
+async fn setup() -> (Indexer, WalletUnlocked, WalletUnlocked) {
+    let wallets = launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+    let provider = wallets[0].provider().unwrap().clone();
+    let indexer = Indexer::new(provider, Store::in_memory().unwrap())
+        .await
+        .unwrap();
+
+    (indexer, wallets[0].clone(), wallets[1].clone())
+}
+
+// Sends the NFT of `order` to its predicate
+async fn fund(seller: &WalletUnlocked, order: &Order) -> Bytes32 {
+    let (tx_id, _) = seller
+        .transfer(
+            &Bech32Address::from(order.predicate_address),
+            1,
+            order.listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    tx_id
+}
+
+// Buys the NFT of `order`, paying the price to the seller and the fee to the treasury
+async fn fill(buyer: &WalletUnlocked, order: &Order) -> Bytes32 {
+    let provider = buyer.provider().unwrap();
+    let listing = &order.listing;
+    let predicate = listing.predicate().with_provider(provider.clone());
+    let buyer_address = Address::from(buyer.address());
+
+    let mut inputs = predicate
+        .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+        .await
+        .unwrap();
+    inputs.extend(
+        buyer
+            .get_asset_inputs_for_amount(ASK_ASSET, PRICE + FEE, None)
+            .await
+            .unwrap(),
+    );
+    inputs.extend(
+        buyer
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await
+            .unwrap(),
+    );
+
+    let outputs = vec![
+        Output::Coin {
+            to: listing.receiver,
+            amount: PRICE,
+            asset_id: ASK_ASSET,
+        },
+        Output::Coin {
+            to: TREASURY,
+            amount: FEE,
+            asset_id: ASK_ASSET,
+        },
+        Output::Coin {
+            to: buyer_address,
+            amount: 1,
+            asset_id: listing.nft_asset_id,
+        },
+        Output::Change {
+            to: buyer_address,
+            amount: 0,
+            asset_id: ASK_ASSET,
+        },
+        Output::Change {
+            to: buyer_address,
+            amount: 0,
+            asset_id: BASE_ASSET,
+        },
+    ];
+
+    submit(buyer, inputs, outputs).await
+}
+
+// Takes the NFT of `order` back, the seller spending the predicate coin next to one of theirs
+async fn cancel(seller: &WalletUnlocked, order: &Order) -> Bytes32 {
+    let provider = seller.provider().unwrap();
+    let listing = &order.listing;
+    let predicate = listing.predicate().with_provider(provider.clone());
+    let seller_address = Address::from(seller.address());
+
+    let mut inputs = predicate
+        .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+        .await
+        .unwrap();
+    inputs.extend(
+        seller
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await
+            .unwrap(),
+    );
+
+    let outputs = [listing.nft_asset_id, BASE_ASSET]
+        .map(|asset_id| Output::Change {
+            to: seller_address,
+            amount: 0,
+            asset_id,
+        })
+        .to_vec();
+
+    submit(seller, inputs, outputs).await
+}
+
+async fn submit(signer: &WalletUnlocked, inputs: Vec<Input>, outputs: Vec<Output>) -> Bytes32 {
+    let provider = signer.provider().unwrap();
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .enable_burn(true);
+    tb.add_signer(signer.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+    let chain_id = provider.consensus_parameters().await.unwrap().chain_id();
+    let tx_id = tx.id(chain_id);
+
+    provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap()
+        .check(None)
+        .unwrap();
+
+    tx_id
+}
+
+mod store {
+
+    use super::*;
+
+    // An order of `nft_asset_id` by `receiver`, not tied to a running node
+    fn offline_order(nft_asset_id: AssetId, receiver: Address) -> Order {
+        let mut listing = ListingParams::new(nft_asset_id, receiver);
+        listing.ask_asset = ASK_ASSET;
+        listing.ask_amount = PRICE;
+
+        Order::from_listing(
+            0,
+            listing,
+            OrderMetadata {
+                created_at: 0,
+                block_height: 0,
+                expires_at: None,
+            },
+        )
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn insert_and_read_back() {
+            let store = Store::in_memory().unwrap();
+            let order = offline_order(NFT_ASSET, Address::new([5u8; 32]));
+
+            assert!(store.insert_order(&order).unwrap());
+            assert!(!store.insert_order(&order).unwrap());
+
+            let listed = store.listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.order, order);
+            assert_eq!(listed.status, ListingStatus::Pending);
+            assert_eq!(listed.funded_tx, None);
+            assert_eq!(
+                store.listings_at(order.predicate_address).unwrap(),
+                vec![listed]
+            );
+        }
+
+        #[test]
+        fn offers_read_back() {
+            let store = Store::in_memory().unwrap();
+            let bidder = Address::new([5u8; 32]);
+            let offers = [
+                Offer::Nft(OfferParams::new(NFT_ASSET, bidder).with_payment(ASK_ASSET, PRICE)),
+                Offer::Collection(
+                    CollectionOfferParams::new(ContractId::new([6u8; 32]), bidder)
+                        .with_payment(ASK_ASSET, PRICE),
+                ),
+            ];
+
+            for offer in &offers {
+                assert!(store.insert_offer(offer).unwrap());
+                assert!(!store.insert_offer(offer).unwrap());
+            }
+
+            assert_eq!(store.offers().unwrap(), offers);
+            assert_ne!(offers[0].predicate_address(), offers[1].predicate_address());
+        }
+
+        #[test]
+        fn query_filters() {
+            let store = Store::in_memory().unwrap();
+            let alice = Address::new([5u8; 32]);
+            let bob = Address::new([6u8; 32]);
+            let orders = [
+                offline_order(NFT_ASSET, alice),
+                offline_order(OTHER_NFT_ASSET, alice),
+                offline_order(NFT_ASSET, bob),
+            ];
+            for order in &orders {
+                store.insert_order(order).unwrap();
+            }
+            store
+                .mark_funded(orders[1].predicate_address, Bytes32::new([7u8; 32]), 3)
+                .unwrap();
+
+            let hashes = |query: ListingQuery| -> Vec<Bytes32> {
+                store
+                    .listings(&query)
+                    .unwrap()
+                    .iter()
+                    .map(|listed| listed.order.hash())
+                    .collect()
+            };
+
+            assert_eq!(hashes(ListingQuery::default()).len(), 3);
+            assert_eq!(
+                hashes(ListingQuery {
+                    receiver: Some(alice),
+                    ..Default::default()
+                }),
+                vec![orders[0].hash(), orders[1].hash()]
+            );
+            assert_eq!(
+                hashes(ListingQuery {
+                    nft_asset_id: Some(NFT_ASSET),
+                    ..Default::default()
+                }),
+                vec![orders[0].hash(), orders[2].hash()]
+            );
+            assert_eq!(
+                hashes(ListingQuery {
+                    status: Some(ListingStatus::Live),
+                    receiver: Some(alice),
+                    ..Default::default()
+                }),
+                vec![orders[1].hash()]
+            );
+        }
+
+        #[test]
+        fn only_live_listings_close() {
+            let store = Store::in_memory().unwrap();
+            let order = offline_order(NFT_ASSET, Address::new([5u8; 32]));
+            let address = order.predicate_address;
+            store.insert_order(&order).unwrap();
+
+            let tx_id = Bytes32::new([7u8; 32]);
+            assert_eq!(
+                store
+                    .mark_closed(address, ListingStatus::Filled, tx_id, 1)
+                    .unwrap(),
+                0
+            );
+            assert_eq!(store.mark_funded(address, tx_id, 2).unwrap(), 1);
+            assert_eq!(store.mark_funded(address, tx_id, 3).unwrap(), 0);
+            assert_eq!(
+                store
+                    .mark_closed(address, ListingStatus::Cancelled, tx_id, 4)
+                    .unwrap(),
+                1
+            );
+
+            let listed = store.listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Cancelled);
+            assert_eq!(listed.updated_height, Some(4));
+        }
+
+        #[test]
+        fn last_height_round_trip() {
+            let store = Store::in_memory().unwrap();
+            assert_eq!(store.last_height().unwrap(), None);
+
+            store.set_last_height(7).unwrap();
+            store.set_last_height(9).unwrap();
+            assert_eq!(store.last_height().unwrap(), Some(9));
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn unknown_status() {
+            assert!("sold".parse::<ListingStatus>().is_err());
+        }
+
+        #[test]
+        fn failed_transaction_rolls_back() {
+            let store = Store::in_memory().unwrap();
+            let order = offline_order(NFT_ASSET, Address::new([5u8; 32]));
+            store.insert_order(&order).unwrap();
+
+            let result: Result<(), StoreError> = store.in_transaction(|store| {
+                store.mark_funded(order.predicate_address, Bytes32::new([7u8; 32]), 3)?;
+                store.set_last_height(3)?;
+                Err(StoreError::UnknownStatus("sold".to_string()))
+            });
+
+            assert!(result.is_err());
+            assert_eq!(store.last_height().unwrap(), None);
+            assert_eq!(
+                store.listing(order.hash()).unwrap().unwrap().status,
+                ListingStatus::Pending
+            );
+        }
+    }
+}
+
+mod indexer {
+
+    use super::*;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn funded_listing_goes_live() {
+            let (indexer, seller, _) = setup().await;
+            let order = order(&indexer, &seller, NFT_ASSET);
+            assert!(indexer.add_order(&order).await.unwrap());
+
+            indexer.sync().await.unwrap();
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Pending);
+
+            let funded_tx = fund(&seller, &order).await;
+            indexer.sync().await.unwrap();
+
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Live);
+            assert_eq!(listed.funded_tx, Some(funded_tx));
+        }
+
+        #[tokio::test]
+        async fn order_added_after_funding() {
+            let (indexer, seller, _) = setup().await;
+            let order = order(&indexer, &seller, NFT_ASSET);
+
+            let funded_tx = fund(&seller, &order).await;
+            indexer.sync().await.unwrap();
+            assert!(indexer.add_order(&order).await.unwrap());
+
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Live);
+            assert_eq!(listed.funded_tx, Some(funded_tx));
+
+            // Syncing again keeps it live
+            indexer.sync().await.unwrap();
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Live);
+        }
+
+        #[tokio::test]
+        async fn filled_listing() {
+            let (indexer, seller, buyer) = setup().await;
+            let order = order(&indexer, &seller, NFT_ASSET);
+            indexer.add_order(&order).await.unwrap();
+
+            fund(&seller, &order).await;
+            let fill_tx = fill(&buyer, &order).await;
+            indexer.sync().await.unwrap();
+
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Filled);
+            assert_eq!(listed.closed_tx, Some(fill_tx));
+        }
+
+        #[tokio::test]
+        async fn cancelled_listing() {
+            let (indexer, seller, _) = setup().await;
+            let order = order(&indexer, &seller, NFT_ASSET);
+            indexer.add_order(&order).await.unwrap();
+
+            fund(&seller, &order).await;
+            indexer.sync().await.unwrap();
+            let cancel_tx = cancel(&seller, &order).await;
+            indexer.sync().await.unwrap();
+
+            let listed = indexer.store().listing(order.hash()).unwrap().unwrap();
+            assert_eq!(listed.status, ListingStatus::Cancelled);
+            assert_eq!(listed.closed_tx, Some(cancel_tx));
+        }
+
+        #[tokio::test]
+        async fn unknown_listings_are_ignored() {
+            let (indexer, seller, _) = setup().await;
+            let known = order(&indexer, &seller, NFT_ASSET);
+            let unknown = order(&indexer, &seller, OTHER_NFT_ASSET);
+            indexer.add_order(&known).await.unwrap();
+
+            fund(&seller, &unknown).await;
+            indexer.sync().await.unwrap();
+
+            assert_eq!(indexer.store().listing(unknown.hash()).unwrap(), None);
+            assert_eq!(
+                indexer
+                    .store()
+                    .listings(&ListingQuery {
+                        status: Some(ListingStatus::Live),
+                        ..Default::default()
+                    })
+                    .unwrap(),
+                vec![]
+            );
+        }
+
+        #[tokio::test]
+        async fn sync_resumes_after_last_block() {
+            let (indexer, seller, _) = setup().await;
+            let order = order(&indexer, &seller, NFT_ASSET);
+            indexer.add_order(&order).await.unwrap();
+
+            let height = indexer.sync().await.unwrap();
+            assert_eq!(indexer.store().last_height().unwrap(), Some(height));
+            assert_eq!(indexer.sync().await.unwrap(), height);
+
+            fund(&seller, &order).await;
+            assert!(indexer.sync().await.unwrap() > height);
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn tampered_order() {
+            let (indexer, seller, _) = setup().await;
+            let mut order = order(&indexer, &seller, NFT_ASSET);
+            order.listing.ask_amount = 1;
+
+            assert!(matches!(
+                indexer.add_order(&order).await,
+                Err(IndexerError::Verify(_))
+            ));
+            assert_eq!(
+                indexer.store().listings(&ListingQuery::default()).unwrap(),
+                vec![]
+            );
+        }
+
+        #[tokio::test]
+        async fn order_for_other_chain() {
+            let (indexer, seller, _) = setup().await;
+            let mut order = order(&indexer, &seller, NFT_ASSET);
+            order.chain_id += 1;
+
+            assert!(matches!(
+                indexer.add_order(&order).await,
+                Err(IndexerError::Verify(_))
+            ));
+        }
+    }
+}
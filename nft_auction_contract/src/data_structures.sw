@@ -0,0 +1,33 @@
+library;
+
+/// The lifecycle of an auction.
+pub enum AuctionState {
+    /// The auction is accepting bids or waiting to be settled.
+    Open: (),
+    /// The NFT and proceeds have been distributed.
+    Settled: (),
+    /// The seller withdrew the NFT before any bid was placed.
+    Cancelled: (),
+}
+
+/// An English auction for a single escrowed NFT.
+pub struct Auction {
+    /// The user that escrowed the NFT and receives the proceeds.
+    pub seller: Identity,
+    /// The NFT held by the contract for the duration of the auction.
+    pub nft_asset: AssetId,
+    /// The asset bids must be placed in.
+    pub ask_asset: AssetId,
+    /// The minimum amount the first bid must meet.
+    pub reserve_price: u64,
+    /// The minimum amount each bid must exceed the previous one by.
+    pub min_increment: u64,
+    /// The block height at which bidding closes.
+    pub end_height: u32,
+    /// The amount of the current highest bid, held in escrow.
+    pub highest_bid: u64,
+    /// The user that placed the current highest bid.
+    pub highest_bidder: Option<Identity>,
+    /// The current state of the auction.
+    pub state: AuctionState,
+}
@@ -0,0 +1,26 @@
+library;
+
+pub enum AccessError {
+    SenderNotSeller: (),
+}
+
+pub enum AuctionError {
+    AuctionDoesNotExist: (),
+    AuctionNotOpen: (),
+    AuctionEnded: (),
+    AuctionNotEnded: (),
+    AuctionHasBids: (),
+}
+
+pub enum BidError {
+    IncorrectAsset: (),
+    BidTooLow: (),
+    SellerCannotBid: (),
+}
+
+pub enum InputError {
+    InvalidNFTAmount: (),
+    InvalidDuration: (),
+    AskAssetIsNFT: (),
+    FeeTooHigh: (),
+}
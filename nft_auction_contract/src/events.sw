@@ -0,0 +1,32 @@
+library;
+
+pub struct AuctionCreated {
+    pub auction_id: u64,
+    pub seller: Identity,
+    pub nft_asset: AssetId,
+    pub ask_asset: AssetId,
+    pub reserve_price: u64,
+    pub end_height: u32,
+}
+
+pub struct BidPlaced {
+    pub auction_id: u64,
+    pub bidder: Identity,
+    pub amount: u64,
+}
+
+pub struct AuctionExtended {
+    pub auction_id: u64,
+    pub end_height: u32,
+}
+
+pub struct AuctionSettled {
+    pub auction_id: u64,
+    pub winner: Option<Identity>,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+pub struct AuctionCancelled {
+    pub auction_id: u64,
+}
@@ -0,0 +1,30 @@
+library;
+
+use ::data_structures::Auction;
+
+abi EnglishAuction {
+    #[payable]
+    #[storage(read, write)]
+    fn create_auction(
+        ask_asset: AssetId,
+        reserve_price: u64,
+        min_increment: u64,
+        duration: u32,
+    ) -> u64;
+
+    #[payable]
+    #[storage(read, write)]
+    fn bid(auction_id: u64);
+
+    #[storage(read, write)]
+    fn settle(auction_id: u64);
+
+    #[storage(read, write)]
+    fn cancel(auction_id: u64);
+
+    #[storage(read)]
+    fn auction_info(auction_id: u64) -> Option<Auction>;
+
+    #[storage(read)]
+    fn total_auctions() -> u64;
+}
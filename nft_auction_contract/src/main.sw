@@ -0,0 +1,404 @@
+contract;
+
+mod data_structures;
+mod errors;
+mod events;
+mod interface;
+
+use data_structures::{Auction, AuctionState};
+use errors::{AccessError, AuctionError, BidError, InputError};
+use events::{AuctionCancelled, AuctionCreated, AuctionExtended, AuctionSettled, BidPlaced};
+use interface::EnglishAuction;
+use std::{asset::transfer, block::height, call_frames::msg_asset_id, context::msg_amount, hash::Hash};
+
+/// The fee of a whole bid, in basis points.
+const MAX_FEE_BPS: u64 = 10_000;
+
+configurable {
+    /// The address that receives the marketplace fee when an auction settles.
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// The marketplace fee taken from the winning bid, in basis points.
+    ///
+    /// # Additional Information
+    ///
+    /// Must not exceed 10_000, auctions cannot be created otherwise.
+    FEE_BPS: u64 = 0,
+    /// Bids placed within this many blocks of the end height extend the auction.
+    ///
+    /// # Additional Information
+    ///
+    /// A value of 0 disables anti-sniping.
+    EXTENSION_WINDOW: u32 = 0,
+    /// The number of blocks after a late bid that bidding stays open for.
+    EXTENSION_DURATION: u32 = 0,
+}
+
+storage {
+    /// The number of auctions that have been created.
+    ///
+    /// # Additional Information
+    ///
+    /// This is also the id that the next auction will be assigned.
+    total_auctions: u64 = 0,
+    /// The auctions created by this contract, keyed by their id.
+    auctions: StorageMap<u64, Auction> = StorageMap {},
+}
+
+impl EnglishAuction for Contract {
+    /// Escrows the forwarded NFT and opens an auction for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ask_asset`: [AssetId] - The asset bids must be placed in.
+    /// * `reserve_price`: [u64] - The minimum amount the first bid must meet.
+    /// * `min_increment`: [u64] - The minimum amount each bid must exceed the previous one by.
+    /// * `duration`: [u32] - The number of blocks bidding stays open for.
+    ///
+    /// # Returns
+    ///
+    /// * [u64] - The id of the newly created auction.
+    ///
+    /// # Reverts
+    ///
+    /// * When the amount forwarded is not exactly one coin.
+    /// * When `duration` is zero.
+    /// * When `ask_asset` is the forwarded NFT.
+    /// * When `FEE_BPS` exceeds 10_000, as settling would then always revert.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    /// * Writes: `2`
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use interface::EnglishAuction;
+    ///
+    /// fn foo(contract_id: ContractId, nft: AssetId, ask_asset: AssetId) {
+    ///     let auction_abi = abi(EnglishAuction, contract_id);
+    ///     let auction_id = auction_abi.create_auction {
+    ///         coins: 1,
+    ///         asset_id: nft.bits(),
+    ///     } (ask_asset, 100, 10, 1_000);
+    /// }
+    /// ```
+    #[payable]
+    #[storage(read, write)]
+    fn create_auction(
+        ask_asset: AssetId,
+        reserve_price: u64,
+        min_increment: u64,
+        duration: u32,
+    ) -> u64 {
+        let nft_asset = msg_asset_id();
+        require(msg_amount() == 1, InputError::InvalidNFTAmount);
+        require(duration > 0, InputError::InvalidDuration);
+        require(ask_asset != nft_asset, InputError::AskAssetIsNFT);
+        require(FEE_BPS <= MAX_FEE_BPS, InputError::FeeTooHigh);
+
+        let seller = msg_sender().unwrap();
+        let auction_id = storage.total_auctions.read();
+        let end_height = height() + duration;
+
+        storage.auctions.insert(
+            auction_id,
+            Auction {
+                seller,
+                nft_asset,
+                ask_asset,
+                reserve_price,
+                min_increment,
+                end_height,
+                highest_bid: 0,
+                highest_bidder: None,
+                state: AuctionState::Open,
+            },
+        );
+        storage.total_auctions.write(auction_id + 1);
+
+        log(AuctionCreated {
+            auction_id,
+            seller,
+            nft_asset,
+            ask_asset,
+            reserve_price,
+            end_height,
+        });
+
+        auction_id
+    }
+
+    /// Places a bid with the forwarded coins, refunding the previous highest bidder.
+    ///
+    /// # Additional Information
+    ///
+    /// A bid placed within `EXTENSION_WINDOW` blocks of the end height keeps bidding open
+    /// for another `EXTENSION_DURATION` blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `auction_id`: [u64] - The id of the auction to bid on.
+    ///
+    /// # Reverts
+    ///
+    /// * When the auction does not exist.
+    /// * When the auction is not open or bidding has closed.
+    /// * When the forwarded asset is not the auction's ask asset.
+    /// * When the seller bids on their own auction.
+    /// * When the bid is below the reserve price or the minimum increment.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    /// * Writes: `1`
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use interface::EnglishAuction;
+    ///
+    /// fn foo(contract_id: ContractId, ask_asset: AssetId) {
+    ///     let auction_abi = abi(EnglishAuction, contract_id);
+    ///     auction_abi.bid {
+    ///         coins: 100,
+    ///         asset_id: ask_asset.bits(),
+    ///     } (0);
+    /// }
+    /// ```
+    #[payable]
+    #[storage(read, write)]
+    fn bid(auction_id: u64) {
+        let mut auction = read_auction(auction_id);
+        require_open(auction.state);
+
+        let current_height = height();
+        require(current_height < auction.end_height, AuctionError::AuctionEnded);
+        require(msg_asset_id() == auction.ask_asset, BidError::IncorrectAsset);
+
+        let bidder = msg_sender().unwrap();
+        require(bidder != auction.seller, BidError::SellerCannotBid);
+
+        let amount = msg_amount();
+        let minimum_bid = match auction.highest_bidder {
+            Some(_) => auction.highest_bid + auction.min_increment,
+            None => auction.reserve_price,
+        };
+        require(
+            amount >= minimum_bid && amount > auction.highest_bid,
+            BidError::BidTooLow,
+        );
+
+        let previous_bidder = auction.highest_bidder;
+        let previous_bid = auction.highest_bid;
+        auction.highest_bidder = Some(bidder);
+        auction.highest_bid = amount;
+
+        let extended_end_height = current_height + EXTENSION_DURATION;
+        let extended = auction.end_height - current_height <= EXTENSION_WINDOW && extended_end_height > auction.end_height;
+        if extended {
+            auction.end_height = extended_end_height;
+        }
+
+        storage.auctions.insert(auction_id, auction);
+
+        if let Some(previous_bidder) = previous_bidder {
+            transfer(previous_bidder, auction.ask_asset, previous_bid);
+        }
+
+        log(BidPlaced {
+            auction_id,
+            bidder,
+            amount,
+        });
+        if extended {
+            log(AuctionExtended {
+                auction_id,
+                end_height: auction.end_height,
+            });
+        }
+    }
+
+    /// Distributes the NFT and the winning bid once bidding has closed.
+    ///
+    /// # Additional Information
+    ///
+    /// The winner receives the NFT, the treasury receives `FEE_BPS` of the winning bid and
+    /// the seller receives the remainder. Without any bids the NFT is returned to the seller.
+    /// Anyone may settle an auction.
+    ///
+    /// # Arguments
+    ///
+    /// * `auction_id`: [u64] - The id of the auction to settle.
+    ///
+    /// # Reverts
+    ///
+    /// * When the auction does not exist.
+    /// * When the auction is not open.
+    /// * When bidding has not closed yet.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    /// * Writes: `1`
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use interface::EnglishAuction;
+    ///
+    /// fn foo(contract_id: ContractId) {
+    ///     let auction_abi = abi(EnglishAuction, contract_id);
+    ///     auction_abi.settle(0);
+    /// }
+    /// ```
+    #[storage(read, write)]
+    fn settle(auction_id: u64) {
+        let mut auction = read_auction(auction_id);
+        require_open(auction.state);
+        require(
+            height() >= auction.end_height,
+            AuctionError::AuctionNotEnded,
+        );
+
+        auction.state = AuctionState::Settled;
+        storage.auctions.insert(auction_id, auction);
+
+        match auction.highest_bidder {
+            Some(winner) => {
+                let fee = calculate_fee(auction.highest_bid);
+                let proceeds = auction.highest_bid - fee;
+
+                transfer(winner, auction.nft_asset, 1);
+                if fee > 0 {
+                    transfer(Identity::Address(TREASURY_ADDRESS), auction.ask_asset, fee);
+                }
+                if proceeds > 0 {
+                    transfer(auction.seller, auction.ask_asset, proceeds);
+                }
+
+                log(AuctionSettled {
+                    auction_id,
+                    winner: Some(winner),
+                    amount: auction.highest_bid,
+                    fee,
+                });
+            },
+            None => {
+                transfer(auction.seller, auction.nft_asset, 1);
+
+                log(AuctionSettled {
+                    auction_id,
+                    winner: None,
+                    amount: 0,
+                    fee: 0,
+                });
+            },
+        }
+    }
+
+    /// Returns the escrowed NFT to the seller of an auction without bids.
+    ///
+    /// # Arguments
+    ///
+    /// * `auction_id`: [u64] - The id of the auction to cancel.
+    ///
+    /// # Reverts
+    ///
+    /// * When the auction does not exist.
+    /// * When the caller is not the seller.
+    /// * When the auction is not open.
+    /// * When a bid has been placed.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    /// * Writes: `1`
+    ///
+    /// # Examples
+    ///
+    /// ```sway
+    /// use interface::EnglishAuction;
+    ///
+    /// fn foo(contract_id: ContractId) {
+    ///     let auction_abi = abi(EnglishAuction, contract_id);
+    ///     auction_abi.cancel(0);
+    /// }
+    /// ```
+    #[storage(read, write)]
+    fn cancel(auction_id: u64) {
+        let mut auction = read_auction(auction_id);
+        require(
+            msg_sender()
+                .unwrap() == auction.seller,
+            AccessError::SenderNotSeller,
+        );
+        require_open(auction.state);
+        require(
+            auction
+                .highest_bidder
+                .is_none(),
+            AuctionError::AuctionHasBids,
+        );
+
+        auction.state = AuctionState::Cancelled;
+        storage.auctions.insert(auction_id, auction);
+
+        transfer(auction.seller, auction.nft_asset, 1);
+
+        log(AuctionCancelled { auction_id });
+    }
+
+    /// Returns the auction with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `auction_id`: [u64] - The id of the auction.
+    ///
+    /// # Returns
+    ///
+    /// * [Option<Auction>] - `Some` auction or `None` when no auction has that id.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    #[storage(read)]
+    fn auction_info(auction_id: u64) -> Option<Auction> {
+        storage.auctions.get(auction_id).try_read()
+    }
+
+    /// Returns the number of auctions that have been created.
+    ///
+    /// # Returns
+    ///
+    /// * [u64] - The number of auctions.
+    ///
+    /// # Number of Storage Accesses
+    ///
+    /// * Reads: `1`
+    #[storage(read)]
+    fn total_auctions() -> u64 {
+        storage.total_auctions.read()
+    }
+}
+
+/// Reads an auction from storage, reverting when it does not exist.
+#[storage(read)]
+fn read_auction(auction_id: u64) -> Auction {
+    let auction = storage.auctions.get(auction_id).try_read();
+    require(auction.is_some(), AuctionError::AuctionDoesNotExist);
+    auction.unwrap()
+}
+
+/// Reverts unless the auction is still open.
+fn require_open(state: AuctionState) {
+    match state {
+        AuctionState::Open => (),
+        _ => require(false, AuctionError::AuctionNotOpen),
+    }
+}
+
+/// Computes `amount * FEE_BPS / MAX_FEE_BPS`, rounded down, without overflowing.
+fn calculate_fee(amount: u64) -> u64 {
+    (amount / MAX_FEE_BPS) * FEE_BPS + (amount % MAX_FEE_BPS) * FEE_BPS / MAX_FEE_BPS
+}
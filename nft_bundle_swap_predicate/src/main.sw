@@ -0,0 +1,127 @@
+predicate;
+
+use std::{
+    inputs::{
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+};
+
+/// the largest number of NFTs a single bundle can hold
+const MAX_BUNDLE_SIZE: u64 = 5;
+
+/// configurable should be set before we deploy predicate
+configurable {
+    FEE_AMOUNT: u64 = 0,
+    FEE_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    ASK_AMOUNT: u64 = 0,
+    ASK_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the number of entries of `NFT_ASSET_IDS` that are part of the bundle
+    NFT_COUNT: u64 = 0,
+    /// the NFTs sold together, only the first `NFT_COUNT` entries are used
+    NFT_ASSET_IDS: [b256; 5] = [
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+    ],
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// checks whether any input of the transaction spends `asset`
+fn is_input_asset(asset: AssetId) -> bool {
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(input_asset) = input_asset_id(i) {
+            if input_asset == asset {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+fn main() -> bool {
+    // Allow cancellation by receiver if they provide an input coin
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == RECEIVER {
+                return true;
+            }
+        }
+        i += 1;
+    }
+
+    if NFT_COUNT == 0 || NFT_COUNT > MAX_BUNDLE_SIZE {
+        return false;
+    }
+
+    // Every NFT of the bundle must be spent together, a partial fill is rejected
+    let mut j = 0;
+    while j < NFT_COUNT {
+        if !is_input_asset(AssetId::from(NFT_ASSET_IDS[j])) {
+            return false;
+        }
+        j += 1;
+    }
+
+    if !is_input_asset(ASK_ASSET) {
+        return false;
+    }
+
+    // Validate output configuration
+    if output_count() < 2 {
+        return false
+    }
+
+    // Ensure both outputs are Coin type
+    match (output_type(0), output_type(1)) {
+        (Some(Output::Coin), Some(Output::Coin)) => (),
+        _ => return false,
+    };
+
+    let output1 = get_output_details(0);
+    let output2 = get_output_details(1);
+
+    match (output1, output2) {
+        (Some((to_reciver, ask_asset, ask_amount)), Some((to_treasury, fee_asset, fee_amount))) => {
+            to_reciver == RECEIVER && ask_asset == ASK_ASSET && ask_amount == ASK_AMOUNT && to_treasury == TREASURY_ADDRESS && fee_asset == FEE_ASSET && fee_amount == FEE_AMOUNT
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,100 @@
+predicate;
+
+use std::{
+    inputs::{
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+};
+
+/// configurable should be set before the bidder funds the predicate
+configurable {
+    FEE_AMOUNT: u64 = 0,
+    FEE_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the asset locked in the predicate as payment for the NFT
+    ASK_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the bidder, who receives the NFT and may cancel the offer
+    RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the NFT contract whose tokens are accepted
+    NFT_CONTRACT_ID: ContractId = ContractId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// `sub_id` is supplied by the acceptor and identifies which token of the collection is delivered
+fn main(sub_id: SubId) -> bool {
+    // Allow cancellation by the bidder if they provide input coins
+    if input_count() == 2 {
+        match (input_coin_owner(0), input_coin_owner(1)) {
+            (Some(owner1), Some(owner2)) => {
+                if owner1 == RECEIVER || owner2 == RECEIVER {
+                    return true;
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    // The delivered NFT must have been minted by the collection contract
+    let nft_asset_id = AssetId::new(NFT_CONTRACT_ID, sub_id);
+
+    // validate input: the locked payment followed by the NFT being delivered
+    match (input_asset_id(0), input_asset_id(1)) {
+        (Some(ask_asset), Some(nft_asset)) => {
+            if ask_asset != ASK_ASSET || nft_asset != nft_asset_id {
+                return false;
+            }
+        },
+        _ => return false,
+    }
+
+    // Validate output configuration
+    if output_count() < 2 {
+        return false
+    }
+
+    // Ensure both outputs are Coin type
+    match (output_type(0), output_type(1)) {
+        (Some(Output::Coin), Some(Output::Coin)) => (),
+        _ => return false,
+    };
+
+    let output1 = get_output_details(0);
+    let output2 = get_output_details(1);
+
+    // The NFT goes to the bidder and the fee to the treasury, the acceptor keeps the rest of the payment
+    match (output1, output2) {
+        (Some((to_bidder, nft_asset, nft_amount)), Some((to_treasury, fee_asset, fee_amount))) => {
+            to_bidder == RECEIVER && nft_asset == nft_asset_id && nft_amount == 1 && to_treasury == TREASURY_ADDRESS && fee_asset == FEE_ASSET && fee_amount == FEE_AMOUNT
+        },
+        _ => false,
+    }
+}
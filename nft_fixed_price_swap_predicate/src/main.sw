@@ -25,8 +25,28 @@ configurable {
     ASK_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
     RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
     NFT_ASSET_ID: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// fee as basis points of `ASK_AMOUNT`, when non zero it replaces `FEE_AMOUNT`
+    FEE_BPS: u64 = 0,
+    /// number of fee recipients paid after the treasury, only that many entries of the arrays below are used
+    EXTRA_FEE_RECIPIENT_COUNT: u64 = 0,
+    EXTRA_FEE_RECIPIENTS: [b256; 3] = [
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+        0x0000000000000000000000000000000000000000000000000000000000000000,
+    ],
+    /// same semantics as `FEE_AMOUNT` and `FEE_BPS`, per extra recipient
+    EXTRA_FEE_AMOUNTS: [u64; 3] = [0, 0, 0],
+    EXTRA_FEE_BPS: [u64; 3] = [0, 0, 0],
+    /// cap on the referral a frontend may charge the taker on top of the price, in basis points of `ASK_AMOUNT`
+    MAX_REFERRAL_BPS: u64 = 0,
 }
 
+/// the largest valid `FEE_BPS`, a fee of the whole price
+const MAX_FEE_BPS: u64 = 10_000;
+
+/// the number of entries in the `EXTRA_FEE_*` arrays
+const MAX_EXTRA_FEE_RECIPIENTS: u64 = 3;
+
 /// extracts output details
 fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
     let to = match output_asset_to(output_index) {
@@ -47,7 +67,72 @@ fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
     Some((to, asset_id, amount))
 }
 
-fn main() -> bool {
+/// fee owed to a recipient, `bps` of `ASK_AMOUNT` rounded down or `amount` when no bps are set
+fn expected_fee(amount: u64, bps: u64) -> u64 {
+    if bps == 0 {
+        return amount;
+    }
+
+    // split the price so that the multiplication cannot overflow
+    (ASK_AMOUNT / MAX_FEE_BPS) * bps + (ASK_AMOUNT % MAX_FEE_BPS) * bps / MAX_FEE_BPS
+}
+
+/// checks that the extra fee recipients are paid in order by the outputs following the treasury output
+fn extra_fees_paid() -> bool {
+    if EXTRA_FEE_RECIPIENT_COUNT > MAX_EXTRA_FEE_RECIPIENTS {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < EXTRA_FEE_RECIPIENT_COUNT {
+        let bps = EXTRA_FEE_BPS[i];
+        if bps > MAX_FEE_BPS {
+            return false;
+        }
+
+        let output_index = 2 + i;
+        match output_type(output_index) {
+            Some(Output::Coin) => (),
+            _ => return false,
+        };
+
+        match get_output_details(output_index) {
+            Some((to, fee_asset, fee_amount)) => {
+                if to != Address::from(EXTRA_FEE_RECIPIENTS[i]) || fee_asset != FEE_ASSET || fee_amount != expected_fee(EXTRA_FEE_AMOUNTS[i], bps) {
+                    return false;
+                }
+            },
+            None => return false,
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// checks that the output following the fee outputs pays `referrer` within the cap
+fn referral_paid(referrer: Address) -> bool {
+    if MAX_REFERRAL_BPS > MAX_FEE_BPS {
+        return false;
+    }
+
+    let output_index = 2 + EXTRA_FEE_RECIPIENT_COUNT;
+    match output_type(output_index) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+
+    match get_output_details(output_index) {
+        Some((to, referral_asset, referral_amount)) => {
+            to == referrer && referral_asset == ASK_ASSET && referral_amount <= expected_fee(0, MAX_REFERRAL_BPS)
+        },
+        None => false,
+    }
+}
+
+/// `referrer` is supplied at fill time by the frontend routing the buyer, `None` when there is no referral
+fn main(referrer: Option<Address>) -> bool {
     // Allow cancellation by receiver if they provide input coins
     if input_count() == 2 {
         match (input_coin_owner(0), input_coin_owner(1)) {
@@ -66,6 +151,10 @@ fn main() -> bool {
         return false;
     }}
 
+    if FEE_BPS > MAX_FEE_BPS {
+        return false
+    }
+
     // Validate output configuration
     if output_count() < 2 {
         return false
@@ -77,15 +166,22 @@ fn main() -> bool {
         _ => return false,
     };
 
+    // A referral is paid by the taker on top of the price, the receiver always gets all of it
+    if let Some(referrer) = referrer {
+        if !referral_paid(referrer) {
+            return false;
+        }
+    }
+
     let output1 = get_output_details(0);
     let output2 = get_output_details(1);
 
     match (output1, output2) {
         (Some((to_reciver, ask_asset, ask_amount)), Some((to_treasury, fee_asset, fee_amount))) => {
             // Check both possible output orderings
-            let valid_case = to_reciver == RECEIVER && ask_asset == ASK_ASSET && ask_amount == ASK_AMOUNT && to_treasury == TREASURY_ADDRESS && fee_asset == FEE_ASSET && fee_amount == FEE_AMOUNT;
+            let valid_case = to_reciver == RECEIVER && ask_asset == ASK_ASSET && ask_amount == ASK_AMOUNT && to_treasury == TREASURY_ADDRESS && fee_asset == FEE_ASSET && fee_amount == expected_fee(FEE_AMOUNT, FEE_BPS);
 
-            valid_case
+            valid_case && extra_fees_paid()
         },
         _ => false,
     }
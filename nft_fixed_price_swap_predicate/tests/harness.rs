@@ -68,3 +68,963 @@ mod revert {
         utils::test_predicate_spend_with_wrong_output().await;
     }
 }
+mod auction {
+
+    use crate::utils::auction::{
+        auction_info, bid, cancel, create_auction, settle, setup_auction, setup_auction_with_fee,
+        total_auctions, AuctionState, BID_ASSET, EXTENSION_DURATION, EXTENSION_WINDOW, FEE_BPS,
+    };
+    use fuels::{accounts::ViewOnlyAccount, prelude::Address, types::Identity};
+
+    const RESERVE_PRICE: u64 = 100;
+    const MIN_INCREMENT: u64 = 10;
+    const DURATION: u32 = 100;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn create_auction_escrows_nft() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let seller_address = Address::from(fixture.seller.address());
+
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            assert_eq!(auction_id, 0);
+            assert_eq!(total_auctions(&seller).await, 1);
+            assert_eq!(fixture.balance(seller_address, fixture.nft_asset).await, 0);
+
+            let auction = auction_info(&seller, auction_id).await.unwrap();
+            assert_eq!(auction.seller, Identity::Address(seller_address));
+            assert_eq!(auction.nft_asset, fixture.nft_asset);
+            assert_eq!(auction.ask_asset, BID_ASSET);
+            assert_eq!(auction.reserve_price, RESERVE_PRICE);
+            assert_eq!(auction.highest_bidder, None);
+            assert_eq!(auction.state, AuctionState::Open);
+        }
+
+        #[tokio::test]
+        async fn outbid_bidder_is_refunded() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let bidder_1_address = Address::from(fixture.bidder_1.address());
+            let bidder_2_address = Address::from(fixture.bidder_2.address());
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let initial_bidder_1_balance = fixture.balance(bidder_1_address, BID_ASSET).await;
+            let initial_bidder_2_balance = fixture.balance(bidder_2_address, BID_ASSET).await;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+            assert_eq!(
+                fixture.balance(bidder_1_address, BID_ASSET).await,
+                initial_bidder_1_balance - RESERVE_PRICE
+            );
+
+            let outbid = RESERVE_PRICE + MIN_INCREMENT;
+            bid(
+                &fixture.instance(&fixture.bidder_2),
+                auction_id,
+                BID_ASSET,
+                outbid,
+            )
+            .await;
+
+            // The first bidder got their escrowed bid back
+            assert_eq!(
+                fixture.balance(bidder_1_address, BID_ASSET).await,
+                initial_bidder_1_balance
+            );
+            assert_eq!(
+                fixture.balance(bidder_2_address, BID_ASSET).await,
+                initial_bidder_2_balance - outbid
+            );
+
+            let auction = auction_info(&seller, auction_id).await.unwrap();
+            assert_eq!(auction.highest_bid, outbid);
+            assert_eq!(
+                auction.highest_bidder,
+                Some(Identity::Address(bidder_2_address))
+            );
+        }
+
+        #[tokio::test]
+        async fn settle_pays_seller_treasury_and_winner() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let seller_address = Address::from(fixture.seller.address());
+            let winner_address = Address::from(fixture.bidder_2.address());
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let initial_seller_balance = fixture.balance(seller_address, BID_ASSET).await;
+            let initial_treasury_balance = fixture.balance(fixture.treasury, BID_ASSET).await;
+
+            let winning_bid = 1_000;
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+            bid(
+                &fixture.instance(&fixture.bidder_2),
+                auction_id,
+                BID_ASSET,
+                winning_bid,
+            )
+            .await;
+
+            let end_height = auction_info(&seller, auction_id).await.unwrap().end_height;
+            fixture.advance_to(end_height).await;
+            settle(&fixture.instance(&fixture.bidder_1), auction_id).await;
+
+            let fee = winning_bid * FEE_BPS / 10_000;
+            assert_eq!(fixture.balance(winner_address, fixture.nft_asset).await, 1);
+            assert_eq!(
+                fixture.balance(fixture.treasury, BID_ASSET).await,
+                initial_treasury_balance + fee
+            );
+            assert_eq!(
+                fixture.balance(seller_address, BID_ASSET).await,
+                initial_seller_balance + winning_bid - fee
+            );
+            assert_eq!(
+                auction_info(&seller, auction_id).await.unwrap().state,
+                AuctionState::Settled
+            );
+        }
+
+        #[tokio::test]
+        async fn settle_without_bids_returns_nft() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let seller_address = Address::from(fixture.seller.address());
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let end_height = auction_info(&seller, auction_id).await.unwrap().end_height;
+            fixture.advance_to(end_height).await;
+            settle(&seller, auction_id).await;
+
+            assert_eq!(fixture.balance(seller_address, fixture.nft_asset).await, 1);
+            assert_eq!(
+                auction_info(&seller, auction_id).await.unwrap().state,
+                AuctionState::Settled
+            );
+        }
+
+        #[tokio::test]
+        async fn cancel_without_bids_returns_nft() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let seller_address = Address::from(fixture.seller.address());
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            cancel(&seller, auction_id).await;
+
+            assert_eq!(fixture.balance(seller_address, fixture.nft_asset).await, 1);
+            assert_eq!(
+                auction_info(&seller, auction_id).await.unwrap().state,
+                AuctionState::Cancelled
+            );
+        }
+
+        #[tokio::test]
+        async fn late_bid_extends_auction() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let end_height = auction_info(&seller, auction_id).await.unwrap().end_height;
+            let bid_height = end_height - EXTENSION_WINDOW + 1;
+            fixture.advance_to(bid_height).await;
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+
+            let auction = auction_info(&seller, auction_id).await.unwrap();
+            assert_eq!(auction.end_height, bid_height + EXTENSION_DURATION);
+
+            // Bidding is still open past the original end height
+            fixture.advance_to(end_height + 1).await;
+            bid(
+                &fixture.instance(&fixture.bidder_2),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE + MIN_INCREMENT,
+            )
+            .await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn bid_below_reserve() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE - 1,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn bid_below_min_increment() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+            bid(
+                &fixture.instance(&fixture.bidder_2),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE + MIN_INCREMENT - 1,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn bid_with_incorrect_asset() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                fuels::prelude::AssetId::zeroed(),
+                RESERVE_PRICE,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn seller_bids_on_own_auction() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(&seller, auction_id, BID_ASSET, RESERVE_PRICE).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn bid_after_end() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let end_height = auction_info(&seller, auction_id).await.unwrap().end_height;
+            fixture.advance_to(end_height).await;
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn settle_before_end() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+            settle(&seller, auction_id).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn settle_twice() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            let end_height = auction_info(&seller, auction_id).await.unwrap().end_height;
+            fixture.advance_to(end_height).await;
+            settle(&seller, auction_id).await;
+            settle(&seller, auction_id).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn cancel_by_non_seller() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            cancel(&fixture.instance(&fixture.bidder_1), auction_id).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn cancel_with_bids() {
+            let fixture = setup_auction().await;
+            let seller = fixture.instance(&fixture.seller);
+            let auction_id = create_auction(
+                &seller,
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await
+            .value;
+
+            bid(
+                &fixture.instance(&fixture.bidder_1),
+                auction_id,
+                BID_ASSET,
+                RESERVE_PRICE,
+            )
+            .await;
+            cancel(&seller, auction_id).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn fee_above_whole_bid() {
+            let fixture = setup_auction_with_fee(10_001).await;
+
+            create_auction(
+                &fixture.instance(&fixture.seller),
+                fixture.nft_asset,
+                RESERVE_PRICE,
+                MIN_INCREMENT,
+                DURATION,
+            )
+            .await;
+        }
+    }
+}
+mod offer {
+
+    use crate::utils::offer::{
+        cancel_offer_as_bidder, test_offer_accept_with_parameters, OFFER_FEE,
+    };
+    use fuels::types::Bytes32;
+
+    const WANTED_SUB_ID: Bytes32 = Bytes32::new([1u8; 32]);
+    const TOP_UP: u64 = 10;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn valid_offer_accept() {
+            test_offer_accept_with_parameters(WANTED_SUB_ID, true, OFFER_FEE, 0, true).await;
+        }
+
+        #[tokio::test]
+        async fn bidder_cancels_offer() {
+            cancel_offer_as_bidder(true).await;
+        }
+
+        #[tokio::test]
+        async fn top_up_refunded_to_bidder() {
+            test_offer_accept_with_parameters(WANTED_SUB_ID, true, OFFER_FEE, TOP_UP, true).await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn incorrect_nft_delivered() {
+            test_offer_accept_with_parameters(Bytes32::new([2u8; 32]), true, OFFER_FEE, 0, true)
+                .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn nft_not_delivered_to_bidder() {
+            test_offer_accept_with_parameters(WANTED_SUB_ID, false, OFFER_FEE, 0, true).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn incorrect_fee_amount() {
+            test_offer_accept_with_parameters(WANTED_SUB_ID, true, OFFER_FEE - 1, 0, true).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn incorrect_bidder_cancels_offer() {
+            cancel_offer_as_bidder(false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn holder_keeps_top_up() {
+            test_offer_accept_with_parameters(WANTED_SUB_ID, true, OFFER_FEE, TOP_UP, false).await;
+        }
+    }
+}
+mod collection_offer {
+
+    use crate::utils::collection_offer::test_collection_offer_accept_with_parameters;
+    use fuels::types::Bytes32;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn accept_with_second_sub_id() {
+            let sub_id = Bytes32::new([2u8; 32]);
+            test_collection_offer_accept_with_parameters(sub_id, sub_id, false).await;
+        }
+
+        #[tokio::test]
+        async fn accept_with_third_sub_id() {
+            let sub_id = Bytes32::new([3u8; 32]);
+            test_collection_offer_accept_with_parameters(sub_id, sub_id, false).await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn delivered_token_does_not_match_sub_id() {
+            test_collection_offer_accept_with_parameters(
+                Bytes32::new([1u8; 32]),
+                Bytes32::new([2u8; 32]),
+                false,
+            )
+            .await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn token_from_other_collection() {
+            let sub_id = Bytes32::new([1u8; 32]);
+            test_collection_offer_accept_with_parameters(sub_id, sub_id, true).await;
+        }
+    }
+}
+mod bundle {
+
+    use crate::utils::bundle::{
+        recover_bundle_as_owner, test_bundle_fill_with_parameters, ASK_AMOUNT,
+    };
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn valid_bundle_fill() {
+            test_bundle_fill_with_parameters(3, ASK_AMOUNT).await;
+        }
+
+        #[tokio::test]
+        async fn owner_recover_bundle() {
+            recover_bundle_as_owner().await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn partial_bundle_fill() {
+            test_bundle_fill_with_parameters(2, ASK_AMOUNT).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn single_item_of_bundle() {
+            test_bundle_fill_with_parameters(1, ASK_AMOUNT).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn incorrect_ask_amount() {
+            test_bundle_fill_with_parameters(3, ASK_AMOUNT - 1).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn duplicate_nft_in_bundle() {
+            let nft_asset_id = fuels::prelude::AssetId::new([9u8; 32]);
+            crate::utils::bundle::BundleParams::new(fuels::prelude::Address::zeroed())
+                .with_nft(nft_asset_id)
+                .with_nft(nft_asset_id);
+        }
+    }
+}
+mod nft_swap {
+
+    use crate::utils::nft_swap::test_nft_swap_with_parameters;
+    use fuels::types::Bytes32;
+
+    const SUB_ID_1: Bytes32 = Bytes32::new([1u8; 32]);
+    const SUB_ID_2: Bytes32 = Bytes32::new([2u8; 32]);
+    const TOP_UP: u64 = 10;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn swap_for_exact_asset() {
+            test_nft_swap_with_parameters(false, SUB_ID_1, true, 0, 0).await;
+        }
+
+        #[tokio::test]
+        async fn swap_for_any_from_collection() {
+            test_nft_swap_with_parameters(true, SUB_ID_2, true, 0, 0).await;
+        }
+
+        #[tokio::test]
+        async fn swap_with_top_up() {
+            test_nft_swap_with_parameters(false, SUB_ID_1, true, TOP_UP, TOP_UP).await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn incorrect_exact_asset() {
+            test_nft_swap_with_parameters(false, SUB_ID_2, true, 0, 0).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn asset_from_other_collection() {
+            test_nft_swap_with_parameters(true, SUB_ID_2, false, 0, 0).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn missing_top_up() {
+            test_nft_swap_with_parameters(false, SUB_ID_1, true, TOP_UP, 0).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn insufficient_top_up() {
+            test_nft_swap_with_parameters(false, SUB_ID_1, true, TOP_UP, TOP_UP - 1).await;
+        }
+    }
+}
+mod fee_policy {
+
+    use crate::utils::fee::{
+        test_fee_bps_agrees_with_predicate, test_fee_split_permutations, FeeError, FeePolicy,
+        FeeSplit, MAX_FEE_BPS,
+    };
+    use fuels::prelude::Address;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn bps_fee_rounds_down() {
+            assert_eq!(FeePolicy::bps(250).fee(1_000), 25);
+            assert_eq!(FeePolicy::bps(250).fee(1_039), 25);
+            assert_eq!(FeePolicy::bps(250).fee(39), 0);
+            assert_eq!(FeePolicy::bps(MAX_FEE_BPS).fee(u64::MAX), u64::MAX);
+            assert_eq!(FeePolicy::Fixed(2).fee(u64::MAX), 2);
+        }
+
+        #[test]
+        fn bps_fee_matches_wide_arithmetic() {
+            let mut rng = StdRng::seed_from_u64(0x6665);
+
+            for _ in 0..100_000 {
+                let price: u64 = rng.gen();
+                let bps = rng.gen_range(0..=MAX_FEE_BPS);
+                let expected = (price as u128 * bps as u128 / MAX_FEE_BPS as u128) as u64;
+
+                assert_eq!(
+                    FeePolicy::bps(bps).fee(price),
+                    expected,
+                    "{price} at {bps} bps"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn predicate_agrees_with_fee_policy() {
+            test_fee_bps_agrees_with_predicate(8).await;
+        }
+
+        #[tokio::test]
+        async fn fee_split_paid_in_order() {
+            test_fee_split_permutations().await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        #[should_panic]
+        fn bps_above_maximum() {
+            FeePolicy::bps(MAX_FEE_BPS + 1);
+        }
+
+        #[test]
+        fn too_many_fee_recipients() {
+            let mut split = FeeSplit::new(Address::zeroed(), FeePolicy::Fixed(1));
+            for i in 1..=3 {
+                split = split
+                    .with_recipient(Address::new([i; 32]), FeePolicy::Fixed(1))
+                    .unwrap();
+            }
+
+            assert_eq!(
+                split.with_recipient(Address::new([4; 32]), FeePolicy::Fixed(1)),
+                Err(FeeError::TooManyRecipients(5))
+            );
+        }
+    }
+}
+mod referral {
+
+    use crate::utils::referral::{test_referral_with_parameters, PRICE};
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn referral_at_cap() {
+            // 1% of the price
+            test_referral_with_parameters(100, PRICE / 100).await;
+        }
+
+        #[tokio::test]
+        async fn referral_below_cap() {
+            test_referral_with_parameters(100, 1).await;
+        }
+
+        #[tokio::test]
+        async fn empty_referral_without_cap() {
+            test_referral_with_parameters(0, 0).await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn referral_over_cap() {
+            test_referral_with_parameters(100, PRICE / 100 + 1).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn referral_without_cap() {
+            test_referral_with_parameters(0, 1).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn cap_above_whole_price() {
+            test_referral_with_parameters(10_001, 1).await;
+        }
+    }
+}
+mod fee_asset {
+
+    use crate::utils::fee::{
+        test_fee_in_base_asset_with_stablecoin_price, test_fill_fee_paid_from_small_coins,
+    };
+    use crate::ASK_ASSET;
+    use fuels::prelude::AssetId;
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn base_asset_fee_with_stablecoin_price() {
+            test_fee_in_base_asset_with_stablecoin_price(AssetId::zeroed()).await;
+        }
+
+        #[tokio::test]
+        async fn fee_paid_from_small_coins() {
+            test_fill_fee_paid_from_small_coins().await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn fee_paid_in_price_asset() {
+            test_fee_in_base_asset_with_stablecoin_price(ASK_ASSET).await;
+        }
+    }
+}
+mod signed_price {
+
+    use crate::utils::signed_price::{
+        test_price_quote_verification, test_reprice_without_relisting,
+        test_signed_price_fill_with_parameters, MIN_NONCE, QUOTED_PRICE,
+    };
+
+    mod success {
+
+        use super::*;
+
+        #[tokio::test]
+        async fn fill_at_signed_price() {
+            test_signed_price_fill_with_parameters(false, QUOTED_PRICE, MIN_NONCE, false).await;
+        }
+
+        #[tokio::test]
+        async fn reprice_without_relisting() {
+            test_reprice_without_relisting().await;
+        }
+
+        #[tokio::test]
+        async fn quotes_verified_off_chain() {
+            test_price_quote_verification().await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn forged_signature() {
+            test_signed_price_fill_with_parameters(true, QUOTED_PRICE, MIN_NONCE, false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn altered_price() {
+            test_signed_price_fill_with_parameters(false, QUOTED_PRICE - 1, MIN_NONCE, false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn nonce_below_minimum() {
+            test_signed_price_fill_with_parameters(false, QUOTED_PRICE, MIN_NONCE - 1, false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn expired_quote() {
+            test_signed_price_fill_with_parameters(false, QUOTED_PRICE, MIN_NONCE, true).await;
+        }
+    }
+}
+mod signed_order {
+
+    use crate::utils::order::{
+        test_order_hash_is_canonical, test_signed_order_fill_with_parameters,
+    };
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn order_hash_is_canonical() {
+            test_order_hash_is_canonical();
+        }
+
+        #[tokio::test]
+        async fn gasless_fill_from_seller_account() {
+            test_signed_order_fill_with_parameters(false, false, false).await;
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[tokio::test]
+        #[should_panic]
+        async fn order_not_signed_by_owner() {
+            test_signed_order_fill_with_parameters(true, false, false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn fill_takes_other_nft_of_account() {
+            test_signed_order_fill_with_parameters(false, true, false).await;
+        }
+
+        #[tokio::test]
+        #[should_panic]
+        async fn expired_order() {
+            test_signed_order_fill_with_parameters(false, false, true).await;
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::utils::{
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    BASE_ASSET,
+};
+use fuels::{
+    accounts::ViewOnlyAccount,
+    prelude::{
+        abigen, launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId,
+        CallParameters, Contract, ContractId, LoadConfiguration, TxPolicies, WalletUnlocked,
+    },
+    programs::responses::CallResponse,
+    test_helpers::WalletsConfig,
+    types::{transaction_builders::VariableOutputPolicy, Bits256, Bytes32, Identity},
+};
+
+abigen!(Contract(
+    name = "NFTAuction",
+    abi = "../nft_auction_contract/out/debug/nft_auction_contract-abi.json"
+));
+
+const AUCTION_CONTRACT_BINARY_PATH: &str =
+    "../nft_auction_contract/out/debug/nft_auction_contract.bin";
+
+/// The asset bids are placed in throughout the auction tests
+pub(crate) const BID_ASSET: AssetId = AssetId::new([1u8; 32]);
+pub(crate) const FEE_BPS: u64 = 250;
+pub(crate) const EXTENSION_WINDOW: u32 = 5;
+pub(crate) const EXTENSION_DURATION: u32 = 10;
+
+/// Everything an auction test needs: a deployed auction contract, an NFT owned by the seller
+/// and funded bidders
+pub(crate) struct AuctionFixture {
+    pub(crate) seller: WalletUnlocked,
+    pub(crate) bidder_1: WalletUnlocked,
+    pub(crate) bidder_2: WalletUnlocked,
+    pub(crate) treasury: Address,
+    pub(crate) contract_id: ContractId,
+    pub(crate) nft_asset: AssetId,
+}
+
+impl AuctionFixture {
+    /// Returns the auction contract instance signed by `wallet`
+    pub(crate) fn instance(&self, wallet: &WalletUnlocked) -> NFTAuction<WalletUnlocked> {
+        NFTAuction::new(self.contract_id, wallet.clone())
+    }
+
+    pub(crate) async fn balance(&self, owner: Address, asset: AssetId) -> u64 {
+        self.seller
+            .provider()
+            .unwrap()
+            .get_asset_balance(&owner.into(), asset)
+            .await
+            .unwrap()
+    }
+
+    /// Produces blocks until the next transaction lands at `height`
+    pub(crate) async fn advance_to(&self, height: u32) {
+        let provider = self.seller.provider().unwrap();
+        let current = provider.latest_block_height().await.unwrap();
+        if height > current + 1 {
+            provider
+                .produce_blocks(height - current - 1, None)
+                .await
+                .unwrap();
+        }
+    }
+}
+
+// Create wallet config for the seller, two bidders and the treasury
+fn configure_wallets() -> WalletsConfig {
+    let assets = [BASE_ASSET, BID_ASSET];
+
+    WalletsConfig::new_multiple_assets(
+        4,
+        assets
+            .map(|asset| AssetConfig {
+                id: asset,
+                num_coins: 1,
+                coin_amount: 1_000_000_000,
+            })
+            .to_vec(),
+    )
+}
+
+/// Deploys the NFT and auction contracts and mints a single NFT to the seller
+pub(crate) async fn setup_auction() -> AuctionFixture {
+    setup_auction_with_fee(FEE_BPS).await
+}
+
+/// Like `setup_auction`, with the auction contract taking `fee_bps` of the winning bid
+pub(crate) async fn setup_auction_with_fee(fee_bps: u64) -> AuctionFixture {
+    let wallets = launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let seller = wallets[0].clone();
+    let bidder_1 = wallets[1].clone();
+    let bidder_2 = wallets[2].clone();
+    let treasury = Address::from(wallets[3].address());
+
+    let (nft_id, nft_instance) = setup(&seller).await;
+    let sub_id = Bytes32::from([1u8; 32]);
+    let seller_identity = Identity::Address(Address::from(seller.address()));
+    constructor(&nft_instance, seller_identity).await;
+    mint(&nft_instance, seller_identity, Bits256(*sub_id), 1).await;
+
+    let configurables = NFTAuctionConfigurables::default()
+        .with_TREASURY_ADDRESS(treasury)
+        .unwrap()
+        .with_FEE_BPS(fee_bps)
+        .unwrap()
+        .with_EXTENSION_WINDOW(EXTENSION_WINDOW)
+        .unwrap()
+        .with_EXTENSION_DURATION(EXTENSION_DURATION)
+        .unwrap();
+
+    let contract_id = Contract::load_from(
+        AUCTION_CONTRACT_BINARY_PATH,
+        LoadConfiguration::default().with_configurables(configurables),
+    )
+    .unwrap()
+    .deploy(&seller, TxPolicies::default())
+    .await
+    .unwrap();
+
+    AuctionFixture {
+        seller,
+        bidder_1,
+        bidder_2,
+        treasury,
+        contract_id: contract_id.into(),
+        nft_asset: get_asset_id(sub_id, nft_id),
+    }
+}
+
+pub(crate) async fn create_auction(
+    contract: &NFTAuction<WalletUnlocked>,
+    nft_asset: AssetId,
+    reserve_price: u64,
+    min_increment: u64,
+    duration: u32,
+) -> CallResponse<u64> {
+    let call_params = CallParameters::new(1, nft_asset, 1_000_000);
+
+    contract
+        .methods()
+        .create_auction(BID_ASSET, reserve_price, min_increment, duration)
+        .call_params(call_params)
+        .unwrap()
+        .call()
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn bid(
+    contract: &NFTAuction<WalletUnlocked>,
+    auction_id: u64,
+    asset: AssetId,
+    amount: u64,
+) -> CallResponse<()> {
+    let call_params = CallParameters::new(amount, asset, 1_000_000);
+
+    contract
+        .methods()
+        .bid(auction_id)
+        .call_params(call_params)
+        .unwrap()
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn settle(
+    contract: &NFTAuction<WalletUnlocked>,
+    auction_id: u64,
+) -> CallResponse<()> {
+    contract
+        .methods()
+        .settle(auction_id)
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn cancel(
+    contract: &NFTAuction<WalletUnlocked>,
+    auction_id: u64,
+) -> CallResponse<()> {
+    contract
+        .methods()
+        .cancel(auction_id)
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+}
+
+pub(crate) async fn auction_info(
+    contract: &NFTAuction<WalletUnlocked>,
+    auction_id: u64,
+) -> Option<Auction> {
+    contract
+        .methods()
+        .auction_info(auction_id)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn total_auctions(contract: &NFTAuction<WalletUnlocked>) -> u64 {
+    contract
+        .methods()
+        .total_auctions()
+        .call()
+        .await
+        .unwrap()
+        .value
+}
@@ -0,0 +1,347 @@
+use crate::utils::{
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    BASE_ASSET,
+};
+use fuels::{
+    accounts::{predicate::Predicate, Account, ViewOnlyAccount},
+    prelude::{
+        abigen, launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, Provider,
+        TxPolicies,
+    },
+    test_helpers::WalletsConfig,
+    types::{
+        input::Input,
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        Bits256, Bytes32, Identity,
+    },
+};
+
+abigen!(Predicate(
+    name = "MyBundlePredicate",
+    abi = "../nft_bundle_swap_predicate/out/debug/nft_bundle_swap_predicate-abi.json"
+));
+
+const BUNDLE_PREDICATE_BINARY: &str =
+    "../nft_bundle_swap_predicate/out/debug/nft_bundle_swap_predicate.bin";
+
+/// The largest bundle the predicate accepts, matching `MAX_BUNDLE_SIZE`
+pub(crate) const MAX_BUNDLE_SIZE: usize = 5;
+
+/// The asset bundles are priced in throughout the bundle tests
+pub(crate) const ASK_ASSET: AssetId = AssetId::new([1u8; 32]);
+pub(crate) const ASK_AMOUNT: u64 = 100;
+pub(crate) const FEE_AMOUNT: u64 = 5;
+
+/// Describes a listing that sells several distinct NFTs for a single price
+#[derive(Debug, Clone)]
+pub(crate) struct BundleParams {
+    pub(crate) nft_asset_ids: Vec<AssetId>,
+    pub(crate) receiver: Address,
+    pub(crate) ask_asset: AssetId,
+    pub(crate) ask_amount: u64,
+    pub(crate) fee_asset: AssetId,
+    pub(crate) fee_amount: u64,
+    pub(crate) treasury: Address,
+}
+
+impl BundleParams {
+    /// Creates an empty bundle listed by `receiver` with no price or fee set
+    pub(crate) fn new(receiver: Address) -> Self {
+        Self {
+            nft_asset_ids: vec![],
+            receiver,
+            ask_asset: AssetId::zeroed(),
+            ask_amount: 0,
+            fee_asset: AssetId::zeroed(),
+            fee_amount: 0,
+            treasury: Address::zeroed(),
+        }
+    }
+
+    /// Adds an NFT to the bundle
+    ///
+    /// Panics when the NFT is already part of the bundle or the bundle is full
+    pub(crate) fn with_nft(mut self, nft_asset_id: AssetId) -> Self {
+        assert!(
+            !self.nft_asset_ids.contains(&nft_asset_id),
+            "{nft_asset_id} is already part of the bundle"
+        );
+        assert!(
+            self.nft_asset_ids.len() < MAX_BUNDLE_SIZE,
+            "a bundle holds at most {MAX_BUNDLE_SIZE} NFTs"
+        );
+        self.nft_asset_ids.push(nft_asset_id);
+        self
+    }
+
+    pub(crate) fn with_price(mut self, ask_asset: AssetId, ask_amount: u64) -> Self {
+        self.ask_asset = ask_asset;
+        self.ask_amount = ask_amount;
+        self
+    }
+
+    pub(crate) fn with_fee(
+        mut self,
+        fee_asset: AssetId,
+        fee_amount: u64,
+        treasury: Address,
+    ) -> Self {
+        self.fee_asset = fee_asset;
+        self.fee_amount = fee_amount;
+        self.treasury = treasury;
+        self
+    }
+
+    pub(crate) fn configurables(&self) -> MyBundlePredicateConfigurables {
+        let mut nft_asset_ids = [Bits256::zeroed(); MAX_BUNDLE_SIZE];
+        for (slot, asset_id) in nft_asset_ids.iter_mut().zip(&self.nft_asset_ids) {
+            *slot = Bits256(**asset_id);
+        }
+
+        MyBundlePredicateConfigurables::default()
+            .with_FEE_AMOUNT(self.fee_amount)
+            .unwrap()
+            .with_FEE_ASSET(self.fee_asset)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_ASK_AMOUNT(self.ask_amount)
+            .unwrap()
+            .with_ASK_ASSET(self.ask_asset)
+            .unwrap()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_COUNT(self.nft_asset_ids.len() as u64)
+            .unwrap()
+            .with_NFT_ASSET_IDS(nft_asset_ids)
+            .unwrap()
+    }
+
+    pub(crate) fn predicate(&self, provider: &Provider) -> Predicate {
+        Predicate::load_from(BUNDLE_PREDICATE_BINARY)
+            .unwrap()
+            .with_configurables(self.configurables())
+            .with_provider(provider.clone())
+    }
+}
+
+// Create wallet config for the seller, the buyer and the treasury
+fn configure_wallets() -> WalletsConfig {
+    let assets = [BASE_ASSET, ASK_ASSET];
+
+    WalletsConfig::new_multiple_assets(
+        3,
+        assets
+            .map(|asset| AssetConfig {
+                id: asset,
+                num_coins: 1,
+                coin_amount: 1_000_000_000,
+            })
+            .to_vec(),
+    )
+}
+
+/// Tests that a bundle of three NFTs can be bought. Parameterized by test cases
+///
+/// The buyer spends the first `filled` NFTs of the bundle and pays `ask_amount`
+pub async fn test_bundle_fill_with_parameters(filled: usize, ask_amount: u64) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let seller_wallet = &wallets[0];
+    let buyer_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let seller_address = Address::from(seller_wallet.address());
+    let buyer_address = Address::from(buyer_wallet.address());
+
+    let (id, instance_1) = setup(seller_wallet).await;
+    let seller_identity = Identity::Address(seller_address);
+    constructor(&instance_1, seller_identity).await;
+
+    let mut bundle = BundleParams::new(seller_address)
+        .with_price(ASK_ASSET, ASK_AMOUNT)
+        .with_fee(ASK_ASSET, FEE_AMOUNT, treasury_address);
+    for sub_id in [[1u8; 32], [2u8; 32], [3u8; 32]] {
+        mint(&instance_1, seller_identity, Bits256(sub_id), 1).await;
+        bundle = bundle.with_nft(get_asset_id(Bytes32::from(sub_id), id));
+    }
+
+    let provider = seller_wallet.provider().unwrap();
+    let predicate = bundle.predicate(provider);
+
+    // The seller locks every NFT of the bundle in the predicate root
+    for nft_asset_id in &bundle.nft_asset_ids {
+        seller_wallet
+            .transfer(predicate.address(), 1, *nft_asset_id, TxPolicies::default())
+            .await
+            .unwrap();
+    }
+
+    let initial_seller_balance = get_balance(provider, seller_wallet.address(), ASK_ASSET).await;
+    let initial_treasury_balance = get_balance(provider, &treasury_address.into(), ASK_ASSET).await;
+
+    let mut inputs: Vec<Input> = vec![];
+    let mut outputs = vec![
+        // Output for the price transferred from the buyer to the seller
+        Output::Coin {
+            to: seller_address,
+            amount: ask_amount,
+            asset_id: ASK_ASSET,
+        },
+        Output::Coin {
+            to: treasury_address,
+            amount: FEE_AMOUNT,
+            asset_id: ASK_ASSET,
+        },
+    ];
+
+    // Spend the NFTs from the predicate and send them to the buyer
+    for nft_asset_id in bundle.nft_asset_ids.iter().take(filled) {
+        inputs.push(
+            predicate
+                .get_asset_inputs_for_amount(*nft_asset_id, 1, None)
+                .await
+                .unwrap()[0]
+                .clone(),
+        );
+        outputs.push(Output::Coin {
+            to: buyer_address,
+            amount: 1,
+            asset_id: *nft_asset_id,
+        });
+    }
+
+    inputs.push(
+        buyer_wallet
+            .get_asset_inputs_for_amount(ASK_ASSET, ask_amount + FEE_AMOUNT, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+    );
+    inputs.push(
+        buyer_wallet
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+    );
+
+    // Change output for unspent asked asset
+    outputs.push(Output::Change {
+        to: buyer_address,
+        amount: 0,
+        asset_id: ASK_ASSET,
+    });
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .enable_burn(true);
+    tb.add_signer(buyer_wallet.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+
+    // Every NFT of the bundle moved to the buyer
+    for nft_asset_id in &bundle.nft_asset_ids {
+        assert_eq!(
+            get_balance(provider, predicate.address(), *nft_asset_id).await,
+            0
+        );
+        assert_eq!(
+            get_balance(provider, buyer_wallet.address(), *nft_asset_id).await,
+            1
+        );
+    }
+
+    assert_eq!(
+        get_balance(provider, seller_wallet.address(), ASK_ASSET).await,
+        initial_seller_balance + ask_amount
+    );
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), ASK_ASSET).await,
+        initial_treasury_balance + FEE_AMOUNT
+    );
+}
+
+// Tests that the whole bundle can be recovered by the seller
+pub async fn recover_bundle_as_owner() {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let wallet = &wallets[0];
+    let wallet_address = Address::from(wallet.address());
+    let provider = wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(wallet).await;
+    let identity = Identity::Address(wallet_address);
+    constructor(&instance_1, identity).await;
+
+    let mut bundle = BundleParams::new(wallet_address);
+    for sub_id in [[1u8; 32], [2u8; 32]] {
+        mint(&instance_1, identity, Bits256(sub_id), 1).await;
+        bundle = bundle.with_nft(get_asset_id(Bytes32::from(sub_id), id));
+    }
+    let predicate = bundle.predicate(provider);
+
+    for nft_asset_id in &bundle.nft_asset_ids {
+        wallet
+            .transfer(predicate.address(), 1, *nft_asset_id, TxPolicies::default())
+            .await
+            .unwrap();
+    }
+
+    let mut inputs: Vec<Input> = vec![];
+    let mut outputs = vec![];
+    for nft_asset_id in &bundle.nft_asset_ids {
+        inputs.push(
+            predicate
+                .get_asset_inputs_for_amount(*nft_asset_id, 1, None)
+                .await
+                .unwrap()[0]
+                .clone(),
+        );
+        // Use a change output to send the unlocked NFT back to the wallet
+        outputs.push(Output::Change {
+            to: wallet_address,
+            amount: 0,
+            asset_id: *nft_asset_id,
+        });
+    }
+    inputs.push(
+        wallet
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+    );
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .enable_burn(true);
+    tb.add_signer(wallet.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+
+    for nft_asset_id in &bundle.nft_asset_ids {
+        assert_eq!(
+            get_balance(provider, predicate.address(), *nft_asset_id).await,
+            0
+        );
+        assert_eq!(
+            get_balance(provider, wallet.address(), *nft_asset_id).await,
+            1
+        );
+    }
+}
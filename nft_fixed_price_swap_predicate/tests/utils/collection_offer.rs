@@ -0,0 +1,192 @@
+use crate::utils::{
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup, setup_with_salt},
+    BASE_ASSET,
+};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{
+        launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, TxPolicies,
+        WalletUnlocked,
+    },
+    test_helpers::WalletsConfig,
+    types::{
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        Bits256, Bytes32, Identity,
+    },
+};
+use nullstate_sdk::offer::CollectionOfferParams;
+
+/// The asset collection offers are paid in throughout the collection offer tests
+pub(crate) const OFFER_ASSET: AssetId = AssetId::new([1u8; 32]);
+pub(crate) const OFFER_AMOUNT: u64 = 42;
+pub(crate) const OFFER_FEE: u64 = 2;
+
+/// Accepts `offer` by delivering `delivered_asset` from `holder` to the bidder, claiming it
+/// is the token minted with `sub_id`
+pub(crate) async fn accept_collection_offer(
+    offer: &CollectionOfferParams,
+    holder: &WalletUnlocked,
+    sub_id: Bytes32,
+    delivered_asset: AssetId,
+) {
+    let provider = holder.provider().unwrap();
+    let predicate = offer
+        .predicate_for_sub_id(sub_id)
+        .with_provider(provider.clone());
+
+    // Get the locked payment
+    let input_predicate = predicate
+        .get_asset_inputs_for_amount(offer.ask_asset, offer.amount, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    // Get the NFT from the holder
+    let input_nft = holder
+        .get_asset_inputs_for_amount(delivered_asset, 1, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    let fee_input = holder
+        .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    // Output for the NFT transferred from the holder to the bidder
+    let output_to_bidder = Output::Coin {
+        to: offer.receiver,
+        amount: 1,
+        asset_id: delivered_asset,
+    };
+
+    let output_to_treasury = Output::Coin {
+        to: offer.treasury,
+        amount: offer.fee_amount,
+        asset_id: offer.fee_asset,
+    };
+
+    // The holder takes whatever payment is left
+    let output_payment_change = Output::Change {
+        to: Address::from(holder.address()),
+        amount: 0,
+        asset_id: offer.ask_asset,
+    };
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(
+        vec![input_predicate, input_nft, fee_input],
+        vec![output_to_bidder, output_to_treasury, output_payment_change],
+        TxPolicies::default(),
+    )
+    .enable_burn(true);
+    tb.add_signer(holder.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+}
+
+// Create wallet config for the bidder, the NFT holder and the treasury
+fn configure_wallets() -> WalletsConfig {
+    let assets = [BASE_ASSET, OFFER_ASSET];
+
+    WalletsConfig::new_multiple_assets(
+        3,
+        assets
+            .map(|asset| AssetConfig {
+                id: asset,
+                num_coins: 1,
+                coin_amount: 1_000_000_000,
+            })
+            .to_vec(),
+    )
+}
+
+/// Tests that a holder can accept a collection offer. Parameterized by test cases
+///
+/// Sub ids `[1u8; 32]`, `[2u8; 32]` and `[3u8; 32]` are minted to the holder in the offered
+/// collection, and `[1u8; 32]` in a second collection. The holder claims `claimed_sub_id` and
+/// delivers `delivered_sub_id` from the offered collection, or from the second one when
+/// `from_other_collection` is set
+pub async fn test_collection_offer_accept_with_parameters(
+    claimed_sub_id: Bytes32,
+    delivered_sub_id: Bytes32,
+    from_other_collection: bool,
+) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let bidder_wallet = &wallets[0];
+    let holder_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let holder_identity = Identity::Address(Address::from(holder_wallet.address()));
+
+    let (collection_id, collection) = setup(holder_wallet).await;
+    constructor(&collection, holder_identity).await;
+    for sub_id in [[1u8; 32], [2u8; 32], [3u8; 32]] {
+        mint(&collection, holder_identity, Bits256(sub_id), 1).await;
+    }
+
+    let (other_collection_id, other_collection) = setup_with_salt(holder_wallet, [1u8; 32]).await;
+    constructor(&other_collection, holder_identity).await;
+    mint(&other_collection, holder_identity, Bits256([1u8; 32]), 1).await;
+
+    let delivered_asset = match from_other_collection {
+        true => get_asset_id(delivered_sub_id, other_collection_id),
+        false => get_asset_id(delivered_sub_id, collection_id),
+    };
+
+    let provider = bidder_wallet.provider().unwrap();
+
+    let offer = CollectionOfferParams::new(collection_id, bidder_wallet.address().into())
+        .with_payment(OFFER_ASSET, OFFER_AMOUNT)
+        .with_fee(OFFER_ASSET, OFFER_FEE, treasury_address);
+    let predicate = offer.predicate().with_provider(provider.clone());
+
+    // The bidder locks the payment in the predicate root
+    bidder_wallet
+        .transfer(
+            predicate.address(),
+            offer.amount,
+            offer.ask_asset,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let initial_holder_balance = get_balance(provider, holder_wallet.address(), OFFER_ASSET).await;
+
+    accept_collection_offer(&offer, holder_wallet, claimed_sub_id, delivered_asset).await;
+
+    // The predicate root's coin has been spent
+    assert_eq!(
+        get_balance(provider, predicate.address(), offer.ask_asset).await,
+        0
+    );
+
+    // The bidder received the claimed token of the collection
+    assert_eq!(
+        get_balance(
+            provider,
+            bidder_wallet.address(),
+            offer.nft_asset_id(claimed_sub_id)
+        )
+        .await,
+        1
+    );
+
+    // The holder was paid the offer minus the fee
+    assert_eq!(
+        get_balance(provider, holder_wallet.address(), OFFER_ASSET).await,
+        initial_holder_balance + offer.proceeds()
+    );
+}
@@ -0,0 +1,347 @@
+use crate::utils::{
+    configure_wallets, get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    BASE_ASSET,
+};
+use crate::ASK_ASSET;
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{
+        launch_custom_provider_and_get_wallets, Address, AssetId, TxPolicies, WalletUnlocked,
+    },
+    types::{Bits256, Bytes32, Identity},
+};
+use nullstate_sdk::{listing::ListingParams, transactions::FillBuilder};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+pub(crate) use nullstate_sdk::{
+    fee::{fee_outputs, FeeError, FeePolicy, FeeSplit},
+    predicate::MAX_FEE_BPS,
+};
+
+
+/// Tests that the predicate accepts exactly the fee `FeePolicy` computes for random prices
+///
+/// For every sample a listing with a random price and bps is created, filling it paying one
+/// less or one more than the computed fee must fail and paying the computed fee must succeed
+pub async fn test_fee_bps_agrees_with_predicate(samples: u8) {
+    let mut rng = StdRng::seed_from_u64(0x6e756c6c);
+
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+
+    for sample in 0..samples {
+        let sub_id = Bytes32::from([sample + 1; 32]);
+        mint(&instance_1, receiver_identity, Bits256(*sub_id), 1).await;
+
+        let price = rng.gen_range(1..=10_000_000);
+        let policy = FeePolicy::bps(rng.gen_range(1..=1_000));
+        let listing =
+            ListingParams::new(get_asset_id(sub_id, id), receiver_wallet.address().into())
+                .with_price(ASK_ASSET, price)
+                .with_fee(ASK_ASSET, policy, treasury_address);
+
+        receiver_wallet
+            .transfer(
+                listing.predicate().address(),
+                1,
+                listing.nft_asset_id,
+                TxPolicies::default(),
+            )
+            .await
+            .unwrap();
+
+        let fee = listing.total_fee();
+        let mut wrong_fees = vec![fee + 1];
+        if fee > 0 {
+            wrong_fees.push(fee - 1);
+        }
+
+        for wrong_fee in wrong_fees {
+            let fill = FillBuilder::new(&listing, taker_wallet).with_fee_amount(wrong_fee);
+            assert!(
+                fill.submit().await.is_err(),
+                "predicate accepted a fee of {wrong_fee} for price {price} at {policy:?}"
+            );
+        }
+
+        FillBuilder::new(&listing, taker_wallet)
+            .submit()
+            .await
+            .unwrap_or_else(|e| {
+                panic!("predicate rejected a fee of {fee} for price {price} at {policy:?}: {e}")
+            });
+    }
+}
+
+/// Tests that the fee split is only accepted when every recipient is paid its own fee in order
+///
+/// The treasury, a referral partner and a creator fund are each owed a distinct amount. Every
+/// other ordering of the recipients, every other assignment of the amounts to them and leaving
+/// out the last recipient must be rejected before the correct split fills the listing
+pub async fn test_fee_split_permutations() {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let referral_address = Address::new([0xaa; 32]);
+    let creator_fund_address = Address::new([0xcc; 32]);
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let split = FeeSplit::new(treasury_address, FeePolicy::Fixed(3))
+        .with_recipient(referral_address, FeePolicy::bps(100))
+        .unwrap()
+        .with_recipient(creator_fund_address, FeePolicy::Fixed(7))
+        .unwrap();
+    let listing = ListingParams::new(get_asset_id(sub_id_1, id), receiver_wallet.address().into())
+        .with_price(ASK_ASSET, 1_000)
+        .with_fee_split(ASK_ASSET, split);
+
+    receiver_wallet
+        .transfer(
+            listing.predicate().address(),
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let correct = listing.fee_amounts();
+    assert_eq!(
+        correct,
+        vec![
+            (treasury_address, 3),
+            (referral_address, 10),
+            (creator_fund_address, 7)
+        ]
+    );
+
+    let mut mis_splits = vec![correct[..2].to_vec()];
+    for permutation in &PERMUTATIONS[1..] {
+        // The recipients are paid out of order
+        mis_splits.push(permutation.iter().map(|&i| correct[i]).collect());
+        // The recipients are in order but paid each other's amounts
+        mis_splits.push(
+            permutation
+                .iter()
+                .enumerate()
+                .map(|(i, &j)| (correct[i].0, correct[j].1))
+                .collect(),
+        );
+    }
+
+    for mis_split in mis_splits {
+        let fill = FillBuilder::new(&listing, taker_wallet).with_fee_amounts(mis_split.clone());
+        assert!(
+            fill.submit().await.is_err(),
+            "predicate accepted the fee split {mis_split:?}"
+        );
+    }
+
+    let mut initial_balances = vec![];
+    for (recipient, _) in &correct {
+        initial_balances.push(get_balance(provider, &(*recipient).into(), ASK_ASSET).await);
+    }
+
+    FillBuilder::new(&listing, taker_wallet)
+        .submit()
+        .await
+        .unwrap();
+
+    for ((recipient, amount), initial_balance) in correct.iter().zip(initial_balances) {
+        assert_eq!(
+            get_balance(provider, &(*recipient).into(), ASK_ASSET).await,
+            initial_balance + amount
+        );
+    }
+}
+
+/// Tests that the price and the fee can be paid in different assets
+///
+/// The listing is priced in a stablecoin, `ASK_ASSET`, while the fee is owed in the base asset,
+/// so the taker pays it out of the same coins as the gas. The taker pays the fee in
+/// `paid_fee_asset`, anything but the base asset must be rejected
+pub async fn test_fee_in_base_asset_with_stablecoin_price(paid_fee_asset: AssetId) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let listing = ListingParams::new(get_asset_id(sub_id_1, id), receiver_wallet.address().into())
+        .with_price(ASK_ASSET, 1_000_000)
+        .with_fee(BASE_ASSET, FeePolicy::bps(250), treasury_address);
+
+    receiver_wallet
+        .transfer(
+            listing.predicate().address(),
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let initial_receiver_balance =
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await;
+    let initial_taker_balance = get_balance(provider, taker_wallet.address(), ASK_ASSET).await;
+    let initial_taker_base_balance =
+        get_balance(provider, taker_wallet.address(), BASE_ASSET).await;
+    let initial_treasury_balance =
+        get_balance(provider, &treasury_address.into(), BASE_ASSET).await;
+
+    FillBuilder::new(&listing, taker_wallet)
+        .with_fee_asset(paid_fee_asset)
+        .submit()
+        .await
+        .unwrap();
+
+    let fee = listing.total_fee();
+    assert_eq!(fee, 25_000);
+
+    // The price moved in the stablecoin only
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), listing.nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await,
+        initial_receiver_balance + listing.ask_amount
+    );
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), ASK_ASSET).await,
+        initial_taker_balance - listing.ask_amount
+    );
+
+    // The fee moved in the base asset, the taker also paid the gas and got the change back
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), BASE_ASSET).await,
+        initial_treasury_balance + fee
+    );
+    let taker_base_balance = get_balance(provider, taker_wallet.address(), BASE_ASSET).await;
+    assert!(taker_base_balance <= initial_taker_base_balance - fee);
+    assert!(taker_base_balance > initial_taker_base_balance - fee - 1_000_000);
+}
+
+/// Tests that the taker pays the real fee of a fill out of as many base asset coins as it takes
+///
+/// The real fee is estimated on a fill by a funded wallet, then a fresh taker receives the price
+/// and three base asset coins, each worth two thirds of that fee. No single coin covers the gas,
+/// so the fill only succeeds when the base coins are selected for the fee itself
+pub async fn test_fill_fee_paid_from_small_coins() {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let funder_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let provider = receiver_wallet.provider().unwrap();
+    let taker_wallet = &WalletUnlocked::new_random(Some(provider.clone()));
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let listing = ListingParams::new(get_asset_id(sub_id_1, id), receiver_wallet.address().into())
+        .with_price(ASK_ASSET, 1_000)
+        .with_fee(ASK_ASSET, FeePolicy::Fixed(10), treasury_address);
+
+    receiver_wallet
+        .transfer(
+            listing.predicate().address(),
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let estimated = FillBuilder::new(&listing, funder_wallet)
+        .build()
+        .await
+        .unwrap();
+    let real_fee = provider
+        .estimate_transaction_cost(estimated, None, None)
+        .await
+        .unwrap()
+        .total_fee;
+    let base_coin = real_fee * 2 / 3;
+
+    funder_wallet
+        .transfer(
+            taker_wallet.address(),
+            listing.ask_amount + listing.total_fee(),
+            ASK_ASSET,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+    for _ in 0..3 {
+        funder_wallet
+            .transfer(
+                taker_wallet.address(),
+                base_coin,
+                BASE_ASSET,
+                TxPolicies::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    FillBuilder::new(&listing, taker_wallet)
+        .submit()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), listing.nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), ASK_ASSET).await,
+        0
+    );
+    assert!(get_balance(provider, taker_wallet.address(), BASE_ASSET).await < base_coin * 3);
+}
@@ -1,4 +1,13 @@
+pub(crate) mod auction;
+pub(crate) mod bundle;
+pub(crate) mod collection_offer;
+pub(crate) mod fee;
 mod interface;
+pub(crate) mod nft_swap;
+pub(crate) mod offer;
+pub(crate) mod order;
+pub(crate) mod referral;
+pub(crate) mod signed_price;
 mod setupnft;
 use fuels::{
     accounts::{predicate::Predicate, Account, ViewOnlyAccount},
@@ -36,6 +45,11 @@ async fn get_balance(provider: &Provider, address: &Bech32Address, asset: AssetI
     provider.get_asset_balance(address, asset).await.unwrap()
 }
 
+// Predicate data for a fill without a referral
+fn no_referral() -> Vec<u8> {
+    MyPredicateEncoder::default().encode_data(None).unwrap()
+}
+
 // Create wallet config for two wallets with base, offered, and ask assets
 fn configure_wallets(asked_asset: AssetId) -> WalletsConfig {
     let assets = [BASE_ASSET, asked_asset];
@@ -106,6 +120,7 @@ pub async fn test_predicate_spend_with_parameters(
                 .with_RECEIVER(receiver_wallet.address().into())
                 .unwrap(),
         )
+        .with_data(no_referral())
         .with_provider(provider.clone());
 
     // Transfer some coins to the predicate root
@@ -252,6 +267,7 @@ pub async fn recover_predicate_as_owner(correct_owner: bool) {
                 .with_RECEIVER(wallets[0].address().into())
                 .unwrap(),
         )
+        .with_data(no_referral())
         .with_provider(provider.clone());
 
     // Transfer some coins to the predicate root
@@ -335,6 +351,7 @@ pub async fn test_predicate_spend_with_wrong_output() {
                 .with_RECEIVER(wallets[0].address().into())
                 .unwrap(),
         )
+        .with_data(no_referral())
         .with_provider(provider.clone());
 
     // Transfer some coins to the predicate root
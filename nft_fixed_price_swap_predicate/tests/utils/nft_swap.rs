@@ -0,0 +1,281 @@
+use crate::utils::{
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup, setup_with_salt},
+    BASE_ASSET,
+};
+use fuels::{
+    accounts::{predicate::Predicate, Account, ViewOnlyAccount},
+    prelude::{
+        abigen, launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, ContractId,
+        Provider, TxPolicies,
+    },
+    test_helpers::WalletsConfig,
+    types::{
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        Bits256, Bytes32, Identity,
+    },
+};
+
+abigen!(Predicate(
+    name = "MyNftSwapPredicate",
+    abi = "../nft_for_nft_swap_predicate/out/debug/nft_for_nft_swap_predicate-abi.json"
+));
+
+const NFT_SWAP_PREDICATE_BINARY: &str =
+    "../nft_for_nft_swap_predicate/out/debug/nft_for_nft_swap_predicate.bin";
+
+/// The asset top-ups are paid in throughout the swap tests
+pub(crate) const TOP_UP_ASSET: AssetId = AssetId::new([1u8; 32]);
+
+/// The counter NFT a maker accepts in exchange for the locked one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WantedNft {
+    /// Exactly this NFT
+    Asset(AssetId),
+    /// Any NFT minted by this contract
+    Collection(ContractId),
+}
+
+/// Describes a listing that swaps the locked NFT for another NFT, optionally with a top-up payment
+#[derive(Debug, Clone)]
+pub(crate) struct NftSwapParams {
+    pub(crate) nft_asset_id: AssetId,
+    pub(crate) receiver: Address,
+    pub(crate) wanted: WantedNft,
+    pub(crate) top_up_asset: AssetId,
+    pub(crate) top_up_amount: u64,
+}
+
+impl NftSwapParams {
+    /// Creates a swap of `nft_asset_id`, listed by `receiver`, for `wanted` without a top-up
+    pub(crate) fn new(nft_asset_id: AssetId, receiver: Address, wanted: WantedNft) -> Self {
+        Self {
+            nft_asset_id,
+            receiver,
+            wanted,
+            top_up_asset: AssetId::zeroed(),
+            top_up_amount: 0,
+        }
+    }
+
+    pub(crate) fn with_top_up(mut self, top_up_asset: AssetId, top_up_amount: u64) -> Self {
+        self.top_up_asset = top_up_asset;
+        self.top_up_amount = top_up_amount;
+        self
+    }
+
+    pub(crate) fn configurables(&self) -> MyNftSwapPredicateConfigurables {
+        let (any_from_collection, wanted_asset_id, wanted_contract_id) = match self.wanted {
+            WantedNft::Asset(asset_id) => (false, asset_id, ContractId::zeroed()),
+            WantedNft::Collection(contract_id) => (true, AssetId::zeroed(), contract_id),
+        };
+
+        MyNftSwapPredicateConfigurables::default()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_ASSET_ID(self.nft_asset_id)
+            .unwrap()
+            .with_ANY_FROM_COLLECTION(any_from_collection)
+            .unwrap()
+            .with_WANTED_ASSET_ID(wanted_asset_id)
+            .unwrap()
+            .with_WANTED_CONTRACT_ID(wanted_contract_id)
+            .unwrap()
+            .with_TOP_UP_AMOUNT(self.top_up_amount)
+            .unwrap()
+            .with_TOP_UP_ASSET(self.top_up_asset)
+            .unwrap()
+    }
+
+    /// The predicate the maker funds, its address does not depend on the sub id
+    pub(crate) fn predicate(&self, provider: &Provider) -> Predicate {
+        self.predicate_for_sub_id(provider, Bytes32::zeroed())
+    }
+
+    /// The predicate with `sub_id` supplied as predicate data, used to fill a collection swap
+    pub(crate) fn predicate_for_sub_id(&self, provider: &Provider, sub_id: Bytes32) -> Predicate {
+        let predicate_data = MyNftSwapPredicateEncoder::default()
+            .encode_data(Bits256(*sub_id))
+            .unwrap();
+
+        Predicate::load_from(NFT_SWAP_PREDICATE_BINARY)
+            .unwrap()
+            .with_configurables(self.configurables())
+            .with_data(predicate_data)
+            .with_provider(provider.clone())
+    }
+}
+
+// Create wallet config for the maker and the taker
+fn configure_wallets() -> WalletsConfig {
+    let assets = [BASE_ASSET, TOP_UP_ASSET];
+
+    WalletsConfig::new_multiple_assets(
+        2,
+        assets
+            .map(|asset| AssetConfig {
+                id: asset,
+                num_coins: 1,
+                coin_amount: 1_000_000_000,
+            })
+            .to_vec(),
+    )
+}
+
+/// Tests that the locked NFT can be swapped for another one. Parameterized by test cases
+///
+/// The maker locks token `[1u8; 32]` of the first collection. When `by_collection` is set they
+/// accept any token of the second collection, otherwise exactly its token `[1u8; 32]`, plus
+/// `required_top_up` of the top-up asset. The taker delivers `delivered_sub_id` from the second
+/// collection, or from the first one when `from_wanted_collection` is false, and pays `paid_top_up`
+pub async fn test_nft_swap_with_parameters(
+    by_collection: bool,
+    delivered_sub_id: Bytes32,
+    from_wanted_collection: bool,
+    required_top_up: u64,
+    paid_top_up: u64,
+) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let maker_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let maker_address = Address::from(maker_wallet.address());
+    let taker_address = Address::from(taker_wallet.address());
+    let maker_identity = Identity::Address(maker_address);
+    let taker_identity = Identity::Address(taker_address);
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let sub_id_2 = Bytes32::from([2u8; 32]);
+
+    // The maker's NFT lives in the first collection, the taker holds tokens of both
+    let (offered_id, offered_collection) = setup(maker_wallet).await;
+    constructor(&offered_collection, maker_identity).await;
+    mint(&offered_collection, maker_identity, Bits256(*sub_id_1), 1).await;
+    mint(&offered_collection, taker_identity, Bits256(*sub_id_2), 1).await;
+
+    let (wanted_id, wanted_collection) = setup_with_salt(taker_wallet, [1u8; 32]).await;
+    constructor(&wanted_collection, taker_identity).await;
+    mint(&wanted_collection, taker_identity, Bits256(*sub_id_1), 1).await;
+    mint(&wanted_collection, taker_identity, Bits256(*sub_id_2), 1).await;
+
+    let offered_asset = get_asset_id(sub_id_1, offered_id);
+    let delivered_asset = match from_wanted_collection {
+        true => get_asset_id(delivered_sub_id, wanted_id),
+        false => get_asset_id(delivered_sub_id, offered_id),
+    };
+
+    let wanted = match by_collection {
+        true => WantedNft::Collection(wanted_id),
+        false => WantedNft::Asset(get_asset_id(sub_id_1, wanted_id)),
+    };
+    let swap = NftSwapParams::new(offered_asset, maker_address, wanted)
+        .with_top_up(TOP_UP_ASSET, required_top_up);
+
+    let provider = maker_wallet.provider().unwrap();
+
+    // Transfer the maker's NFT to the predicate root
+    maker_wallet
+        .transfer(
+            swap.predicate(provider).address(),
+            1,
+            offered_asset,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let initial_maker_top_up_balance =
+        get_balance(provider, maker_wallet.address(), TOP_UP_ASSET).await;
+
+    let predicate = swap.predicate_for_sub_id(provider, delivered_sub_id);
+
+    let mut inputs = vec![
+        predicate
+            .get_asset_inputs_for_amount(offered_asset, 1, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+        taker_wallet
+            .get_asset_inputs_for_amount(delivered_asset, 1, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+    ];
+
+    // Output for the counter NFT transferred from the taker to the maker
+    let mut outputs = vec![Output::Coin {
+        to: maker_address,
+        amount: 1,
+        asset_id: delivered_asset,
+    }];
+
+    if paid_top_up > 0 {
+        inputs.push(
+            taker_wallet
+                .get_asset_inputs_for_amount(TOP_UP_ASSET, paid_top_up, None)
+                .await
+                .unwrap()[0]
+                .clone(),
+        );
+        outputs.push(Output::Coin {
+            to: maker_address,
+            amount: paid_top_up,
+            asset_id: TOP_UP_ASSET,
+        });
+        outputs.push(Output::Change {
+            to: taker_address,
+            amount: 0,
+            asset_id: TOP_UP_ASSET,
+        });
+    }
+
+    inputs.push(
+        taker_wallet
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await
+            .unwrap()[0]
+            .clone(),
+    );
+
+    // Output for the locked NFT transferred from the predicate to the taker
+    outputs.push(Output::Coin {
+        to: taker_address,
+        amount: 1,
+        asset_id: offered_asset,
+    });
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .enable_burn(true);
+    tb.add_signer(taker_wallet.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+
+    // The NFTs changed hands
+    assert_eq!(
+        get_balance(provider, predicate.address(), offered_asset).await,
+        0
+    );
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), offered_asset).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, maker_wallet.address(), delivered_asset).await,
+        1
+    );
+
+    // The maker received the top-up
+    assert_eq!(
+        get_balance(provider, maker_wallet.address(), TOP_UP_ASSET).await,
+        initial_maker_top_up_balance + required_top_up
+    );
+}
@@ -0,0 +1,280 @@
+use crate::utils::{
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    BASE_ASSET,
+};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, TxPolicies},
+    test_helpers::WalletsConfig,
+    types::{
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        Bits256, Bytes32, Identity,
+    },
+};
+use nullstate_sdk::offer::OfferParams;
+
+/// The asset offers are paid in throughout the offer tests
+pub(crate) const OFFER_ASSET: AssetId = AssetId::new([1u8; 32]);
+pub(crate) const OFFER_AMOUNT: u64 = 42;
+pub(crate) const OFFER_FEE: u64 = 2;
+
+// Create wallet config for the bidder, the NFT holder and the treasury
+fn configure_wallets() -> WalletsConfig {
+    let assets = [BASE_ASSET, OFFER_ASSET];
+
+    WalletsConfig::new_multiple_assets(
+        3,
+        assets
+            .map(|asset| AssetConfig {
+                id: asset,
+                num_coins: 1,
+                coin_amount: 1_000_000_000,
+            })
+            .to_vec(),
+    )
+}
+
+/// Tests that an NFT holder can accept an offer. Parameterized by test cases
+///
+/// The offer is made for the NFT minted with sub id `[1u8; 32]`. The holder delivers
+/// `delivered_sub_id` to the bidder, or to themselves when `deliver_to_bidder` is false,
+/// and pays `fee_amount` to the treasury. When `top_up` is non zero the bidder funds the offer
+/// with a second coin of that amount, which the holder refunds to the bidder, or keeps when
+/// `refund_to_bidder` is false
+pub async fn test_offer_accept_with_parameters(
+    delivered_sub_id: Bytes32,
+    deliver_to_bidder: bool,
+    fee_amount: u64,
+    top_up: u64,
+    refund_to_bidder: bool,
+) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let bidder_wallet = &wallets[0];
+    let holder_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let bidder_address = Address::from(bidder_wallet.address());
+    let holder_address = Address::from(holder_wallet.address());
+
+    let (id, instance_1) = setup(holder_wallet).await;
+    let holder_identity = Identity::Address(holder_address);
+    constructor(&instance_1, holder_identity).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let sub_id_2 = Bytes32::from([2u8; 32]);
+    mint(&instance_1, holder_identity, Bits256(*sub_id_1), 1).await;
+    mint(&instance_1, holder_identity, Bits256(*sub_id_2), 1).await;
+    let wanted_asset = get_asset_id(sub_id_1, id);
+    let delivered_asset = get_asset_id(delivered_sub_id, id);
+
+    let provider = bidder_wallet.provider().unwrap();
+
+    let offer = OfferParams::new(wanted_asset, bidder_address)
+        .with_payment(OFFER_ASSET, OFFER_AMOUNT)
+        .with_fee(OFFER_ASSET, OFFER_FEE, treasury_address);
+    let predicate = offer.predicate().with_provider(provider.clone());
+
+    // The bidder locks the payment in the predicate root, topping it up with a second coin
+    for amount in [offer.amount, top_up] {
+        if amount == 0 {
+            continue;
+        }
+        bidder_wallet
+            .transfer(
+                predicate.address(),
+                amount,
+                offer.ask_asset,
+                TxPolicies::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let initial_bidder_balance = get_balance(provider, bidder_wallet.address(), OFFER_ASSET).await;
+    let initial_holder_balance = get_balance(provider, holder_wallet.address(), OFFER_ASSET).await;
+    let initial_treasury_balance =
+        get_balance(provider, &treasury_address.into(), OFFER_ASSET).await;
+
+    // Get every locked coin, the first one leads the inputs
+    let mut inputs_predicate = predicate
+        .get_asset_inputs_for_amount(offer.ask_asset, offer.amount + top_up, None)
+        .await
+        .unwrap();
+    assert_eq!(inputs_predicate.len(), if top_up > 0 { 2 } else { 1 });
+    let input_predicate = inputs_predicate.remove(0);
+
+    // Get the NFT from the holder
+    let input_nft = holder_wallet
+        .get_asset_inputs_for_amount(delivered_asset, 1, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    let fee_input = holder_wallet
+        .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    // Output for the NFT transferred from the holder to the bidder
+    let output_to_bidder = Output::Coin {
+        to: if deliver_to_bidder {
+            bidder_address
+        } else {
+            holder_address
+        },
+        amount: 1,
+        asset_id: delivered_asset,
+    };
+
+    let output_to_treasury = Output::Coin {
+        to: treasury_address,
+        amount: fee_amount,
+        asset_id: offer.fee_asset,
+    };
+
+    // The holder is paid the offer less the fee
+    let output_to_holder = Output::Coin {
+        to: holder_address,
+        amount: offer.proceeds(),
+        asset_id: offer.ask_asset,
+    };
+
+    let mut outputs = vec![output_to_bidder, output_to_treasury, output_to_holder];
+    if top_up > 0 {
+        // The top-up goes back to the bidder
+        outputs.push(Output::Coin {
+            to: if refund_to_bidder {
+                bidder_address
+            } else {
+                holder_address
+            },
+            amount: top_up,
+            asset_id: offer.ask_asset,
+        });
+    }
+
+    let mut inputs = vec![input_predicate, input_nft, fee_input];
+    inputs.extend(inputs_predicate);
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+        .enable_burn(true);
+    tb.add_signer(holder_wallet.clone()).unwrap();
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+
+    // The predicate root's coin has been spent
+    assert_eq!(
+        get_balance(provider, predicate.address(), offer.ask_asset).await,
+        0
+    );
+
+    // The bidder received the NFT
+    assert_eq!(
+        get_balance(provider, bidder_wallet.address(), wanted_asset).await,
+        1
+    );
+
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), OFFER_ASSET).await,
+        initial_treasury_balance + offer.fee_amount
+    );
+
+    // The holder was paid the offer minus the fee
+    assert_eq!(
+        get_balance(provider, holder_wallet.address(), OFFER_ASSET).await,
+        initial_holder_balance + offer.proceeds()
+    );
+
+    // The bidder got the top-up back
+    assert_eq!(
+        get_balance(provider, bidder_wallet.address(), OFFER_ASSET).await,
+        initial_bidder_balance + top_up
+    );
+}
+
+// Tests that the offer can be cancelled by the bidder
+// `correct_bidder` is a boolean flag to set in order to test passing and failing conditions
+pub async fn cancel_offer_as_bidder(correct_bidder: bool) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(), None, None)
+        .await
+        .unwrap();
+
+    let bidder_wallet = &wallets[0];
+    let wallet = match correct_bidder {
+        true => &wallets[0],
+        false => &wallets[1],
+    };
+
+    let provider = wallet.provider().unwrap();
+
+    let offer = OfferParams::new(AssetId::new([7u8; 32]), bidder_wallet.address().into())
+        .with_payment(OFFER_ASSET, OFFER_AMOUNT);
+    let predicate = offer.predicate().with_provider(provider.clone());
+
+    bidder_wallet
+        .transfer(
+            predicate.address(),
+            offer.amount,
+            offer.ask_asset,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let initial_wallet_balance = get_balance(provider, wallet.address(), OFFER_ASSET).await;
+
+    // Get predicate input
+    let input_predicate = predicate
+        .get_asset_inputs_for_amount(offer.ask_asset, offer.amount, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    // Get input from wallet
+    let input_from_wallet = wallet
+        .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+        .await
+        .unwrap()[0]
+        .clone();
+
+    // Use a change output to send the unlocked payment to the wallet
+    let output_payment_change = Output::Change {
+        to: Address::from(wallet.address()),
+        amount: 0,
+        asset_id: offer.ask_asset,
+    };
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(
+        vec![input_predicate, input_from_wallet],
+        vec![output_payment_change],
+        TxPolicies::default(),
+    )
+    .enable_burn(true);
+    tb.add_signer(wallet.clone()).unwrap();
+
+    let tx = tb.build(provider).await.unwrap();
+
+    let _tx_status = provider
+        .send_transaction_and_await_commit(tx)
+        .await
+        .unwrap();
+
+    // The predicate root's coin has been spent
+    let predicate_balance = get_balance(provider, predicate.address(), offer.ask_asset).await;
+    assert_eq!(predicate_balance, 0);
+
+    // The locked payment is back with the wallet
+    let wallet_balance = get_balance(provider, wallet.address(), OFFER_ASSET).await;
+    assert_eq!(wallet_balance, initial_wallet_balance + offer.amount);
+}
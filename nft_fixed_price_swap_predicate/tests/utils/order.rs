@@ -0,0 +1,363 @@
+use crate::utils::{
+    configure_wallets,
+    fee::{fee_outputs, FeePolicy, MAX_FEE_BPS},
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    signed_price::{b512_to_signature, signature_to_b512},
+    BASE_ASSET,
+};
+use crate::ASK_ASSET;
+use fuels::{
+    accounts::{predicate::Predicate, Account, ViewOnlyAccount},
+    core::traits::Signer,
+    crypto::Message,
+    prelude::{
+        abigen, launch_custom_provider_and_get_wallets, Address, AssetId, Provider, Result,
+        TxPolicies, WalletUnlocked,
+    },
+    types::{
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        tx_status::TxStatus,
+        Bits256, Bytes32, Identity, B512,
+    },
+};
+use sha2::{Digest, Sha256};
+
+abigen!(Predicate(
+    name = "MySellerAccountPredicate",
+    abi = "../nft_seller_account_predicate/out/debug/nft_seller_account_predicate-abi.json"
+));
+
+const SELLER_ACCOUNT_PREDICATE_BINARY: &str =
+    "../nft_seller_account_predicate/out/debug/nft_seller_account_predicate.bin";
+
+/// The price of the orders in the signed order tests
+pub(crate) const ORDER_PRICE: u64 = 1_000;
+/// The marketplace fee of the seller accounts in the signed order tests
+pub(crate) const ORDER_FEE_BPS: u64 = 250;
+
+/// Describes the predicate a seller keeps their NFTs in to list them without paying gas
+///
+/// Listing an NFT held by the account only takes signing an `Order` off-chain, the buyer's fill
+/// moves the NFT out of the account. The owner can spend anything the account holds by adding a
+/// coin of their own to the transaction, which also cancels every order for the spent NFTs
+#[derive(Debug, Clone)]
+pub(crate) struct SellerAccount {
+    pub(crate) owner: Address,
+    pub(crate) treasury: Address,
+    pub(crate) fee_bps: u64,
+}
+
+impl SellerAccount {
+    /// Panics when `fee_bps` exceeds `MAX_FEE_BPS`, the predicate rejects such accounts
+    pub(crate) fn new(owner: Address, treasury: Address, fee_bps: u64) -> Self {
+        assert!(
+            fee_bps <= MAX_FEE_BPS,
+            "fee of {fee_bps} bps exceeds {MAX_FEE_BPS}"
+        );
+
+        Self {
+            owner,
+            treasury,
+            fee_bps,
+        }
+    }
+
+    pub(crate) fn configurables(&self) -> MySellerAccountPredicateConfigurables {
+        MySellerAccountPredicateConfigurables::default()
+            .with_OWNER(self.owner)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_FEE_BPS(self.fee_bps)
+            .unwrap()
+    }
+
+    /// The account predicate, its address does not depend on the order being filled
+    pub(crate) fn predicate(&self, provider: &Provider) -> Predicate {
+        let unsigned = Order::new(AssetId::zeroed(), AssetId::zeroed(), 0, 0, 0)
+            .with_signature(B512::from((Bits256::zeroed(), Bits256::zeroed())));
+        self.predicate_with_order(provider, &unsigned)
+    }
+
+    /// The account predicate with `signed` supplied as predicate data, used to fill the order
+    pub(crate) fn predicate_with_order(
+        &self,
+        provider: &Provider,
+        signed: &SignedOrder,
+    ) -> Predicate {
+        let predicate_data = MySellerAccountPredicateEncoder::default()
+            .encode_data(signed.order.clone(), signed.signature)
+            .unwrap();
+
+        Predicate::load_from(SELLER_ACCOUNT_PREDICATE_BINARY)
+            .unwrap()
+            .with_configurables(self.configurables())
+            .with_data(predicate_data)
+            .with_provider(provider.clone())
+    }
+
+    /// The fee the treasury is owed when `order` is filled
+    pub(crate) fn fee(&self, order: &Order) -> u64 {
+        FeePolicy::Bps(self.fee_bps).fee(order.ask_amount)
+    }
+}
+
+/// An order together with the owner's signature over its hash
+#[derive(Debug, Clone)]
+pub(crate) struct SignedOrder {
+    pub(crate) order: Order,
+    pub(crate) signature: B512,
+}
+
+impl SignedOrder {
+    /// Checks that the order was signed by `owner`
+    pub(crate) fn is_signed_by(&self, owner: Address) -> bool {
+        let message = Message::from_bytes(*self.order.hash());
+
+        b512_to_signature(&self.signature)
+            .recover(&message)
+            .is_ok_and(|signer| Address::from(*signer.hash()) == owner)
+    }
+}
+
+impl Order {
+    pub(crate) fn new(
+        nft_asset_id: AssetId,
+        ask_asset: AssetId,
+        ask_amount: u64,
+        nonce: u64,
+        expiry: u32,
+    ) -> Self {
+        Self {
+            nft_asset_id,
+            ask_asset,
+            ask_amount,
+            nonce,
+            expiry,
+        }
+    }
+
+    /// The canonical hash of the order, must match `order_hash` in the predicate
+    ///
+    /// This is the sha256 of the NFT asset id and the ask asset followed by the price, nonce and
+    /// expiry as big endian u64
+    pub(crate) fn hash(&self) -> Bytes32 {
+        let mut hasher = Sha256::new();
+        hasher.update(*self.nft_asset_id);
+        hasher.update(*self.ask_asset);
+        hasher.update(self.ask_amount.to_be_bytes());
+        hasher.update(self.nonce.to_be_bytes());
+        hasher.update(u64::from(self.expiry).to_be_bytes());
+
+        Bytes32::from(<[u8; 32]>::from(hasher.finalize()))
+    }
+
+    fn with_signature(self, signature: B512) -> SignedOrder {
+        SignedOrder {
+            order: self,
+            signature,
+        }
+    }
+
+    /// Signs the order with the wallet of the account owner
+    pub(crate) async fn sign(self, owner: &WalletUnlocked) -> Result<SignedOrder> {
+        let message = Message::from_bytes(*self.hash());
+        let signature = owner.sign(message).await?;
+
+        Ok(self.with_signature(signature_to_b512(&signature)))
+    }
+}
+
+/// Fills a signed order, moving the NFT straight out of the seller account
+///
+/// Any `extra_inputs` of the account are spent along and sent to the taker, which the predicate
+/// must reject. The transaction expires together with the order
+pub(crate) async fn fill_signed_order(
+    account: &SellerAccount,
+    signed: &SignedOrder,
+    taker: &WalletUnlocked,
+    extra_inputs: &[AssetId],
+) -> Result<TxStatus> {
+    let provider = taker.provider().expect("taker wallet has a provider");
+    let predicate = account.predicate_with_order(provider, signed);
+    let taker_address = Address::from(taker.address());
+    let order = &signed.order;
+    let fee = account.fee(order);
+
+    let mut inputs = vec![];
+    let mut outputs = vec![Output::Coin {
+        to: account.owner,
+        amount: order.ask_amount,
+        asset_id: order.ask_asset,
+    }];
+    outputs.extend(fee_outputs(&[(account.treasury, fee)], order.ask_asset));
+
+    for nft_asset_id in std::iter::once(&order.nft_asset_id).chain(extra_inputs) {
+        inputs.extend(
+            predicate
+                .get_asset_inputs_for_amount(*nft_asset_id, 1, None)
+                .await?,
+        );
+        outputs.push(Output::Coin {
+            to: taker_address,
+            amount: 1,
+            asset_id: *nft_asset_id,
+        });
+    }
+
+    inputs.extend(
+        taker
+            .get_asset_inputs_for_amount(order.ask_asset, order.ask_amount + fee, None)
+            .await?,
+    );
+    inputs.extend(
+        taker
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await?,
+    );
+    outputs.extend(
+        [order.ask_asset, BASE_ASSET].map(|asset_id| Output::Change {
+            to: taker_address,
+            amount: 0,
+            asset_id,
+        }),
+    );
+
+    let tx_policies = TxPolicies::default().with_expiration(order.expiry);
+    let mut tb =
+        ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies).enable_burn(true);
+    tb.add_signer(taker.clone())?;
+    let tx = tb.build(provider).await?;
+
+    provider.send_transaction_and_await_commit(tx).await
+}
+
+/// Tests that a signed order is filled straight from the seller account. Parameterized by test cases
+///
+/// Two NFTs are minted into the seller account and the seller signs an order for the first one
+/// without sending any transaction. When `forged` is set the buyer signs the order instead, when
+/// `take_extra_nft` is set the buyer also spends the second NFT and when `expired` is set the
+/// order expired before the fill
+pub async fn test_signed_order_fill_with_parameters(
+    forged: bool,
+    take_extra_nft: bool,
+    expired: bool,
+) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let seller_wallet = &wallets[0];
+    let buyer_wallet = &wallets[1];
+    let seller_address = Address::from(seller_wallet.address());
+    let treasury_address = Address::from(wallets[2].address());
+    let provider = seller_wallet.provider().unwrap();
+
+    let account = SellerAccount::new(seller_address, treasury_address, ORDER_FEE_BPS);
+    let account_predicate = account.predicate(provider);
+    let account_identity = Identity::Address(account_predicate.address().into());
+
+    // The NFTs are minted straight into the seller account
+    let (id, instance_1) = setup(seller_wallet).await;
+    constructor(&instance_1, Identity::Address(seller_address)).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let sub_id_2 = Bytes32::from([2u8; 32]);
+    mint(&instance_1, account_identity, Bits256(*sub_id_1), 1).await;
+    mint(&instance_1, account_identity, Bits256(*sub_id_2), 1).await;
+    let nft_asset_id = get_asset_id(sub_id_1, id);
+    let extra_nft_asset_id = get_asset_id(sub_id_2, id);
+
+    let height = provider.latest_block_height().await.unwrap();
+    let expiry = match expired {
+        true => height - 1,
+        false => height + 100,
+    };
+
+    // Listing is signing, the seller sends no transaction
+    let signer = match forged {
+        true => buyer_wallet,
+        false => seller_wallet,
+    };
+    let signed = Order::new(nft_asset_id, ASK_ASSET, ORDER_PRICE, 0, expiry)
+        .sign(signer)
+        .await
+        .unwrap();
+    assert_eq!(signed.is_signed_by(seller_address), !forged);
+
+    let initial_seller_base_balance =
+        get_balance(provider, seller_wallet.address(), BASE_ASSET).await;
+    let initial_seller_balance = get_balance(provider, seller_wallet.address(), ASK_ASSET).await;
+    let initial_treasury_balance = get_balance(provider, &treasury_address.into(), ASK_ASSET).await;
+
+    let extra_inputs = match take_extra_nft {
+        true => vec![extra_nft_asset_id],
+        false => vec![],
+    };
+    fill_signed_order(&account, &signed, buyer_wallet, &extra_inputs)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_balance(provider, account_predicate.address(), nft_asset_id).await,
+        0
+    );
+    assert_eq!(
+        get_balance(provider, account_predicate.address(), extra_nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, buyer_wallet.address(), nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, seller_wallet.address(), ASK_ASSET).await,
+        initial_seller_balance + ORDER_PRICE
+    );
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), ASK_ASSET).await,
+        initial_treasury_balance + account.fee(&signed.order)
+    );
+
+    // The seller paid no gas at any point of the sale
+    assert_eq!(
+        get_balance(provider, seller_wallet.address(), BASE_ASSET).await,
+        initial_seller_base_balance
+    );
+}
+
+/// Tests that the order hash commits to every field of the order in a fixed layout
+pub fn test_order_hash_is_canonical() {
+    let order = Order::new(AssetId::new([1u8; 32]), ASK_ASSET, ORDER_PRICE, 7, 500);
+
+    let mut preimage = vec![];
+    preimage.extend_from_slice(&[1u8; 32]);
+    preimage.extend_from_slice(&*ASK_ASSET);
+    preimage.extend_from_slice(&ORDER_PRICE.to_be_bytes());
+    preimage.extend_from_slice(&7u64.to_be_bytes());
+    preimage.extend_from_slice(&500u64.to_be_bytes());
+    assert_eq!(
+        order.hash(),
+        Bytes32::from(<[u8; 32]>::from(Sha256::digest(&preimage)))
+    );
+    assert_eq!(order.hash(), order.clone().hash());
+
+    let altered = [
+        Order::new(AssetId::new([2u8; 32]), ASK_ASSET, ORDER_PRICE, 7, 500),
+        Order::new(AssetId::new([1u8; 32]), BASE_ASSET, ORDER_PRICE, 7, 500),
+        Order::new(AssetId::new([1u8; 32]), ASK_ASSET, ORDER_PRICE + 1, 7, 500),
+        Order::new(AssetId::new([1u8; 32]), ASK_ASSET, ORDER_PRICE, 8, 500),
+        Order::new(AssetId::new([1u8; 32]), ASK_ASSET, ORDER_PRICE, 7, 501),
+    ];
+    for other in altered {
+        assert_ne!(
+            order.hash(),
+            other.hash(),
+            "{other:?} hashes like {order:?}"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::utils::{
+    configure_wallets,
+    fee::FeePolicy,
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+};
+use crate::ASK_ASSET;
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{launch_custom_provider_and_get_wallets, Address, TxPolicies},
+    types::{Bits256, Bytes32, Identity},
+};
+use nullstate_sdk::{listing::ListingParams, transactions::FillBuilder};
+
+/// The price of the listing in the referral tests
+pub(crate) const PRICE: u64 = 10_000;
+/// The fee paid to the treasury on top of the price
+pub(crate) const TREASURY_FEE: u64 = 2;
+
+/// Tests that a frontend can charge a referral on top of the price. Parameterized by test cases
+///
+/// The listing allows referrals of up to `max_referral_bps` of `PRICE`, the taker fills it paying
+/// `referral` to a referrer chosen at fill time. The receiver must still get the whole price
+pub async fn test_referral_with_parameters(max_referral_bps: u64, referral: u64) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let referrer_address = Address::new([0xee; 32]);
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let listing = ListingParams::new(get_asset_id(sub_id_1, id), receiver_wallet.address().into())
+        .with_price(ASK_ASSET, PRICE)
+        .with_fee(ASK_ASSET, FeePolicy::Fixed(TREASURY_FEE), treasury_address)
+        .with_max_referral_bps(max_referral_bps);
+
+    // The predicate address does not depend on the referrer chosen at fill time
+    assert_eq!(
+        listing.predicate().address(),
+        listing
+            .predicate_with_referrer(Some(referrer_address))
+            .address()
+    );
+
+    receiver_wallet
+        .transfer(
+            listing.predicate().address(),
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let initial_receiver_balance =
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await;
+    let initial_treasury_balance = get_balance(provider, &treasury_address.into(), ASK_ASSET).await;
+
+    FillBuilder::new(&listing, taker_wallet)
+        .with_referral(referrer_address, referral)
+        .submit()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), listing.nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await,
+        initial_receiver_balance + PRICE
+    );
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), ASK_ASSET).await,
+        initial_treasury_balance + TREASURY_FEE
+    );
+    assert_eq!(
+        get_balance(provider, &referrer_address.into(), ASK_ASSET).await,
+        referral
+    );
+}
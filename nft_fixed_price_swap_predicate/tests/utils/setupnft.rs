@@ -1,38 +1,21 @@
-use fuels::{
-    prelude::{
-        abigen, Contract, ContractId,
-        LoadConfiguration, TxPolicies, WalletUnlocked,
-    },
-    types::{AssetId, Bytes32},
-};
-use sha2::{Digest, Sha256};
+use fuels::prelude::{ContractId, WalletUnlocked};
+use nullstate_sdk::collection::{deploy_collection, CollectionConfig};
 
-abigen!(Contract(
-    name = "NFT",
-    abi = "../NFT-contract/out/debug/NFT-contract-abi.json"
-),);
-
-const NFT_CONTRACT_BINARY_PATH: &str = "../NFT-contract/out/debug/NFT-contract.bin";
+pub(crate) use nullstate_sdk::collection::{get_asset_id, Metadata, State, NFT};
 
 pub(crate) async fn setup(wallet: &WalletUnlocked) -> (
     ContractId,
     NFT<WalletUnlocked>,
 ) {
-
-    let id = Contract::load_from(NFT_CONTRACT_BINARY_PATH, LoadConfiguration::default())
-        .unwrap()
-        .deploy(wallet, TxPolicies::default())
-        .await
-        .unwrap();
-
-    let instance_1 = NFT::new(id.clone(), wallet.clone());
-
-    (id.into(), instance_1)
+    setup_with_salt(wallet, [0u8; 32]).await
 }
 
-pub(crate) fn get_asset_id(sub_id: Bytes32, contract: ContractId) -> AssetId {
-    let mut hasher = Sha256::new();
-    hasher.update(*contract);
-    hasher.update(*sub_id);
-    AssetId::new(*Bytes32::from(<[u8; 32]>::from(hasher.finalize())))
+/// Deploys another NFT contract, a distinct `salt` gives a distinct collection
+pub(crate) async fn setup_with_salt(wallet: &WalletUnlocked, salt: [u8; 32]) -> (
+    ContractId,
+    NFT<WalletUnlocked>,
+) {
+    deploy_collection(wallet, &CollectionConfig::new(salt))
+        .await
+        .unwrap()
 }
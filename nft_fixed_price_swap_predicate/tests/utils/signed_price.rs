@@ -0,0 +1,523 @@
+use crate::utils::{
+    configure_wallets,
+    fee::{fee_outputs, FeePolicy},
+    get_balance,
+    interface::{constructor, mint},
+    setupnft::{get_asset_id, setup},
+    BASE_ASSET,
+};
+use crate::ASK_ASSET;
+use fuels::{
+    accounts::{predicate::Predicate, Account, ViewOnlyAccount},
+    core::traits::Signer,
+    crypto::{Message, Signature},
+    prelude::{
+        abigen, launch_custom_provider_and_get_wallets, Address, AssetId, Provider, Result,
+        TxPolicies, WalletUnlocked,
+    },
+    types::{
+        output::Output,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        tx_status::TxStatus,
+        Bits256, Bytes32, Identity, B512,
+    },
+};
+use sha2::{Digest, Sha256};
+
+abigen!(Predicate(
+    name = "MySignedPricePredicate",
+    abi = "../nft_signed_price_predicate/out/debug/nft_signed_price_predicate-abi.json"
+));
+
+const SIGNED_PRICE_PREDICATE_BINARY: &str =
+    "../nft_signed_price_predicate/out/debug/nft_signed_price_predicate.bin";
+
+/// The price the seller quotes in the signed price tests
+pub(crate) const QUOTED_PRICE: u64 = 100;
+/// The lowest nonce the listing in the signed price tests accepts
+pub(crate) const MIN_NONCE: u64 = 1;
+
+/// Describes a listing whose price is set by quotes the seller signs off-chain
+///
+/// Repricing only takes a new signature, the NFT stays locked at the same predicate address.
+/// Quotes cannot be revoked on-chain, an older quote stays valid until it expires unless the
+/// listing is moved to a predicate with a higher `min_nonce`
+#[derive(Debug, Clone)]
+pub(crate) struct SignedPriceParams {
+    pub(crate) nft_asset_id: AssetId,
+    pub(crate) receiver: Address,
+    pub(crate) ask_asset: AssetId,
+    pub(crate) min_nonce: u64,
+    pub(crate) fee_asset: AssetId,
+    pub(crate) fee: FeePolicy,
+    pub(crate) treasury: Address,
+}
+
+impl SignedPriceParams {
+    /// Creates a listing of `nft_asset_id` by `receiver`, priced in `ask_asset`, with no fee set
+    pub(crate) fn new(nft_asset_id: AssetId, receiver: Address, ask_asset: AssetId) -> Self {
+        Self {
+            nft_asset_id,
+            receiver,
+            ask_asset,
+            min_nonce: 0,
+            fee_asset: AssetId::zeroed(),
+            fee: FeePolicy::Fixed(0),
+            treasury: Address::zeroed(),
+        }
+    }
+
+    pub(crate) fn with_min_nonce(mut self, min_nonce: u64) -> Self {
+        self.min_nonce = min_nonce;
+        self
+    }
+
+    pub(crate) fn with_fee(
+        mut self,
+        fee_asset: AssetId,
+        fee: FeePolicy,
+        treasury: Address,
+    ) -> Self {
+        self.fee_asset = fee_asset;
+        self.fee = fee;
+        self.treasury = treasury;
+        self
+    }
+
+    pub(crate) fn configurables(&self) -> MySignedPricePredicateConfigurables {
+        let (fee_amount, fee_bps) = match self.fee {
+            FeePolicy::Fixed(amount) => (amount, 0),
+            FeePolicy::Bps(bps) => (0, bps),
+        };
+
+        MySignedPricePredicateConfigurables::default()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_ASSET_ID(self.nft_asset_id)
+            .unwrap()
+            .with_ASK_ASSET(self.ask_asset)
+            .unwrap()
+            .with_MIN_NONCE(self.min_nonce)
+            .unwrap()
+            .with_FEE_AMOUNT(fee_amount)
+            .unwrap()
+            .with_FEE_ASSET(self.fee_asset)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_FEE_BPS(fee_bps)
+            .unwrap()
+    }
+
+    /// The predicate the seller funds, its address does not depend on the quote
+    pub(crate) fn predicate(&self, provider: &Provider) -> Predicate {
+        let quote = PriceQuote::new(0, 0, 0);
+        self.predicate_with_quote(
+            provider,
+            &quote,
+            B512::from((Bits256::zeroed(), Bits256::zeroed())),
+        )
+    }
+
+    /// The predicate with `quote` and its `signature` supplied as predicate data
+    pub(crate) fn predicate_with_quote(
+        &self,
+        provider: &Provider,
+        quote: &PriceQuote,
+        signature: B512,
+    ) -> Predicate {
+        let predicate_data = MySignedPricePredicateEncoder::default()
+            .encode_data(quote.clone(), signature)
+            .unwrap();
+
+        Predicate::load_from(SIGNED_PRICE_PREDICATE_BINARY)
+            .unwrap()
+            .with_configurables(self.configurables())
+            .with_data(predicate_data)
+            .with_provider(provider.clone())
+    }
+}
+
+/// Why a quote must not be used to fill a listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QuoteError {
+    /// The seller has since signed a quote with a higher nonce
+    StaleNonce { nonce: u64, latest_nonce: u64 },
+    /// The quote expired before `height`
+    Expired { expiry: u32, height: u32 },
+    /// The signature was not made by the seller over this quote
+    ForgedSignature,
+}
+
+impl PriceQuote {
+    pub(crate) fn new(price: u64, nonce: u64, expiry: u32) -> Self {
+        Self {
+            price,
+            nonce,
+            expiry,
+        }
+    }
+
+    /// The digest the seller signs, must match `quote_digest` in the predicate
+    ///
+    /// This is the sha256 of the NFT asset id, the ask asset, and the price, nonce and expiry as
+    /// big endian u64
+    pub(crate) fn digest(&self, listing: &SignedPriceParams) -> Bytes32 {
+        let mut hasher = Sha256::new();
+        hasher.update(*listing.nft_asset_id);
+        hasher.update(*listing.ask_asset);
+        hasher.update(self.price.to_be_bytes());
+        hasher.update(self.nonce.to_be_bytes());
+        hasher.update(u64::from(self.expiry).to_be_bytes());
+
+        Bytes32::from(<[u8; 32]>::from(hasher.finalize()))
+    }
+
+    /// Signs the quote for `listing` with the seller's wallet
+    pub(crate) async fn sign(
+        &self,
+        listing: &SignedPriceParams,
+        seller: &WalletUnlocked,
+    ) -> Result<B512> {
+        let message = Message::from_bytes(*self.digest(listing));
+        let signature = seller.sign(message).await?;
+
+        Ok(signature_to_b512(&signature))
+    }
+
+    /// Checks `signature` and freshness of the quote before a frontend offers it to buyers
+    ///
+    /// `latest_nonce` is the highest nonce seen from the seller for this listing and `height` the
+    /// current block height
+    pub(crate) fn verify(
+        &self,
+        listing: &SignedPriceParams,
+        signature: &B512,
+        latest_nonce: u64,
+        height: u32,
+    ) -> std::result::Result<(), QuoteError> {
+        if self.nonce < latest_nonce.max(listing.min_nonce) {
+            return Err(QuoteError::StaleNonce {
+                nonce: self.nonce,
+                latest_nonce,
+            });
+        }
+
+        if self.expiry < height {
+            return Err(QuoteError::Expired {
+                expiry: self.expiry,
+                height,
+            });
+        }
+
+        let message = Message::from_bytes(*self.digest(listing));
+        let signer = b512_to_signature(signature)
+            .recover(&message)
+            .map_err(|_| QuoteError::ForgedSignature)?;
+
+        match Address::from(*signer.hash()) == listing.receiver {
+            true => Ok(()),
+            false => Err(QuoteError::ForgedSignature),
+        }
+    }
+}
+
+/// Converts a signature into the `B512` predicates take as data
+pub(crate) fn signature_to_b512(signature: &Signature) -> B512 {
+    let bytes: [u8; 64] = **signature;
+    B512::from((
+        Bits256(bytes[..32].try_into().unwrap()),
+        Bits256(bytes[32..].try_into().unwrap()),
+    ))
+}
+
+/// Converts a `B512` taken by a predicate back into a signature
+pub(crate) fn b512_to_signature(signature: &B512) -> Signature {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&signature.bytes[0].0);
+    bytes[32..].copy_from_slice(&signature.bytes[1].0);
+    Signature::from_bytes(bytes)
+}
+
+/// Buys the NFT of `listing` at the price of `quote`
+///
+/// The transaction expires together with the quote, the predicate cannot read the block height
+pub(crate) async fn fill_with_quote(
+    listing: &SignedPriceParams,
+    taker: &WalletUnlocked,
+    quote: &PriceQuote,
+    signature: B512,
+) -> Result<TxStatus> {
+    let provider = taker.provider().expect("taker wallet has a provider");
+    let predicate = listing.predicate_with_quote(provider, quote, signature);
+    let taker_address = Address::from(taker.address());
+    let fee = listing.fee.fee(quote.price);
+
+    let mut inputs = predicate
+        .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+        .await?;
+    inputs.extend(
+        taker
+            .get_asset_inputs_for_amount(listing.ask_asset, quote.price + fee, None)
+            .await?,
+    );
+    inputs.extend(
+        taker
+            .get_asset_inputs_for_amount(BASE_ASSET, 1, None)
+            .await?,
+    );
+
+    // Output for the quoted price transferred from the taker to the receiver
+    let mut outputs = vec![Output::Coin {
+        to: listing.receiver,
+        amount: quote.price,
+        asset_id: listing.ask_asset,
+    }];
+    outputs.extend(fee_outputs(&[(listing.treasury, fee)], listing.fee_asset));
+    outputs.extend([
+        // Output for the NFT transferred from the predicate to the taker
+        Output::Coin {
+            to: taker_address,
+            amount: 1,
+            asset_id: listing.nft_asset_id,
+        },
+        Output::Change {
+            to: taker_address,
+            amount: 0,
+            asset_id: listing.ask_asset,
+        },
+        Output::Change {
+            to: taker_address,
+            amount: 0,
+            asset_id: BASE_ASSET,
+        },
+    ]);
+
+    let tx_policies = TxPolicies::default().with_expiration(quote.expiry);
+    let mut tb =
+        ScriptTransactionBuilder::prepare_transfer(inputs, outputs, tx_policies).enable_burn(true);
+    tb.add_signer(taker.clone())?;
+    let tx = tb.build(provider).await?;
+
+    provider.send_transaction_and_await_commit(tx).await
+}
+
+/// Tests that a listing can be bought at a price the seller signed. Parameterized by test cases
+///
+/// The seller quotes `QUOTED_PRICE` with `nonce` on a listing that requires `MIN_NONCE`. When
+/// `forged` is set the taker signs the quote instead of the seller, when `expired` is set the
+/// quote expired before the fill. The taker submits the quote with `submitted_price`
+pub async fn test_signed_price_fill_with_parameters(
+    forged: bool,
+    submitted_price: u64,
+    nonce: u64,
+    expired: bool,
+) {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let treasury_address = Address::from(wallets[2].address());
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let listing = SignedPriceParams::new(
+        get_asset_id(sub_id_1, id),
+        receiver_wallet.address().into(),
+        ASK_ASSET,
+    )
+    .with_min_nonce(MIN_NONCE)
+    .with_fee(ASK_ASSET, FeePolicy::bps(500), treasury_address);
+
+    receiver_wallet
+        .transfer(
+            listing.predicate(provider).address(),
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let height = provider.latest_block_height().await.unwrap();
+    let expiry = match expired {
+        true => height - 1,
+        false => height + 100,
+    };
+
+    let signer = match forged {
+        true => taker_wallet,
+        false => receiver_wallet,
+    };
+    let signature = PriceQuote::new(QUOTED_PRICE, nonce, expiry)
+        .sign(&listing, signer)
+        .await
+        .unwrap();
+
+    let initial_receiver_balance =
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await;
+    let initial_treasury_balance = get_balance(provider, &treasury_address.into(), ASK_ASSET).await;
+
+    let quote = PriceQuote::new(submitted_price, nonce, expiry);
+    fill_with_quote(&listing, taker_wallet, &quote, signature)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), listing.nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await,
+        initial_receiver_balance + QUOTED_PRICE
+    );
+    assert_eq!(
+        get_balance(provider, &treasury_address.into(), ASK_ASSET).await,
+        initial_treasury_balance + listing.fee.fee(QUOTED_PRICE)
+    );
+}
+
+/// Tests that a seller can reprice a listing without moving the NFT
+///
+/// The seller quotes a first price and then a lower one with the next nonce. The first quote is
+/// stale for a frontend that has seen the second one, the NFT is bought at the second price from
+/// the address it was listed at
+pub async fn test_reprice_without_relisting() {
+    let wallets = &launch_custom_provider_and_get_wallets(configure_wallets(ASK_ASSET), None, None)
+        .await
+        .unwrap();
+
+    let receiver_wallet = &wallets[0];
+    let taker_wallet = &wallets[1];
+    let provider = receiver_wallet.provider().unwrap();
+
+    let (id, instance_1) = setup(receiver_wallet).await;
+    let sub_id_1 = Bytes32::from([1u8; 32]);
+    let receiver_identity = Identity::Address(Address::from(receiver_wallet.address()));
+    constructor(&instance_1, receiver_identity).await;
+    mint(&instance_1, receiver_identity, Bits256(*sub_id_1), 1).await;
+
+    let listing = SignedPriceParams::new(
+        get_asset_id(sub_id_1, id),
+        receiver_wallet.address().into(),
+        ASK_ASSET,
+    )
+    .with_min_nonce(MIN_NONCE);
+    let predicate_address = listing.predicate(provider).address().clone();
+
+    receiver_wallet
+        .transfer(
+            &predicate_address,
+            1,
+            listing.nft_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let height = provider.latest_block_height().await.unwrap();
+    let first_quote = PriceQuote::new(QUOTED_PRICE, MIN_NONCE, height + 100);
+    let first_signature = first_quote.sign(&listing, receiver_wallet).await.unwrap();
+    let second_quote = PriceQuote::new(QUOTED_PRICE / 2, MIN_NONCE + 1, height + 100);
+    let second_signature = second_quote.sign(&listing, receiver_wallet).await.unwrap();
+
+    assert_eq!(
+        first_quote.verify(&listing, &first_signature, MIN_NONCE + 1, height),
+        Err(QuoteError::StaleNonce {
+            nonce: MIN_NONCE,
+            latest_nonce: MIN_NONCE + 1
+        })
+    );
+    assert_eq!(
+        second_quote.verify(&listing, &second_signature, MIN_NONCE + 1, height),
+        Ok(())
+    );
+
+    let initial_receiver_balance =
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await;
+
+    fill_with_quote(&listing, taker_wallet, &second_quote, second_signature)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_balance(provider, &predicate_address, listing.nft_asset_id).await,
+        0
+    );
+    assert_eq!(
+        get_balance(provider, taker_wallet.address(), listing.nft_asset_id).await,
+        1
+    );
+    assert_eq!(
+        get_balance(provider, receiver_wallet.address(), ASK_ASSET).await,
+        initial_receiver_balance + QUOTED_PRICE / 2
+    );
+}
+
+/// Tests that quotes are checked off-chain like the predicate checks them on-chain
+pub async fn test_price_quote_verification() {
+    let seller = WalletUnlocked::new_random(None);
+    let forger = WalletUnlocked::new_random(None);
+    let listing =
+        SignedPriceParams::new(AssetId::new([7u8; 32]), seller.address().into(), ASK_ASSET)
+            .with_min_nonce(MIN_NONCE);
+
+    let quote = PriceQuote::new(QUOTED_PRICE, 3, 1_000);
+    let signature = quote.sign(&listing, &seller).await.unwrap();
+
+    assert_eq!(quote.verify(&listing, &signature, 3, 1_000), Ok(()));
+
+    // A quote older than the latest one or below the listing's minimum nonce
+    assert_eq!(
+        quote.verify(&listing, &signature, 4, 1_000),
+        Err(QuoteError::StaleNonce {
+            nonce: 3,
+            latest_nonce: 4
+        })
+    );
+    let below_minimum = PriceQuote::new(QUOTED_PRICE, 0, 1_000);
+    let below_minimum_signature = below_minimum.sign(&listing, &seller).await.unwrap();
+    assert!(matches!(
+        below_minimum.verify(&listing, &below_minimum_signature, 0, 1_000),
+        Err(QuoteError::StaleNonce { .. })
+    ));
+
+    assert_eq!(
+        quote.verify(&listing, &signature, 3, 1_001),
+        Err(QuoteError::Expired {
+            expiry: 1_000,
+            height: 1_001
+        })
+    );
+
+    // Signed by someone else, or the signed terms were altered
+    let forged_signature = quote.sign(&listing, &forger).await.unwrap();
+    assert_eq!(
+        quote.verify(&listing, &forged_signature, 3, 1_000),
+        Err(QuoteError::ForgedSignature)
+    );
+    let altered = PriceQuote::new(1, 3, 1_000);
+    assert_eq!(
+        altered.verify(&listing, &signature, 3, 1_000),
+        Err(QuoteError::ForgedSignature)
+    );
+
+    // The quote only signs for the NFT it was made for
+    let other_listing = SignedPriceParams {
+        nft_asset_id: AssetId::new([8u8; 32]),
+        ..listing.clone()
+    };
+    assert_eq!(
+        quote.verify(&other_listing, &signature, 3, 1_000),
+        Err(QuoteError::ForgedSignature)
+    );
+}
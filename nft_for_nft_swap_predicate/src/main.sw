@@ -0,0 +1,108 @@
+predicate;
+
+use std::{
+    inputs::{
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+};
+
+/// configurable should be set before we deploy predicate
+configurable {
+    /// the maker, who receives the counter NFT and may cancel the swap
+    RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the NFT locked in the predicate
+    NFT_ASSET_ID: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// when set any token of `WANTED_CONTRACT_ID` is accepted instead of `WANTED_ASSET_ID`
+    ANY_FROM_COLLECTION: bool = false,
+    WANTED_ASSET_ID: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    WANTED_CONTRACT_ID: ContractId = ContractId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// an optional payment the taker adds on top of the counter NFT, 0 disables it
+    TOP_UP_AMOUNT: u64 = 0,
+    TOP_UP_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// checks that the output at `output_index` is a coin of `amount` of `asset` sent to the maker
+fn is_paid_to_receiver(output_index: u64, asset: AssetId, amount: u64) -> bool {
+    match output_type(output_index) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+
+    match get_output_details(output_index) {
+        Some((to, output_asset, output_amount)) => to == RECEIVER && output_asset == asset && output_amount == amount,
+        None => false,
+    }
+}
+
+/// `sub_id` is supplied by the taker in collection mode and ignored otherwise
+fn main(sub_id: SubId) -> bool {
+    // Allow cancellation by receiver if they provide an input coin
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == RECEIVER {
+                return true;
+            }
+        }
+        i += 1;
+    }
+
+    // validate input
+    match input_asset_id(0) {
+        Some(nft_asset) => {
+            if nft_asset != NFT_ASSET_ID {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    let wanted_asset = if ANY_FROM_COLLECTION {
+        AssetId::new(WANTED_CONTRACT_ID, sub_id)
+    } else {
+        WANTED_ASSET_ID
+    };
+
+    // Validate output configuration
+    if output_count() < 1 || (TOP_UP_AMOUNT > 0 && output_count() < 2) {
+        return false
+    }
+
+    // The counter NFT goes to the maker, followed by the top-up payment if one is required
+    if !is_paid_to_receiver(0, wanted_asset, 1) {
+        return false;
+    }
+
+    TOP_UP_AMOUNT == 0 || is_paid_to_receiver(1, TOP_UP_ASSET, TOP_UP_AMOUNT)
+}
@@ -0,0 +1,162 @@
+predicate;
+
+use std::{
+    inputs::{
+        input_amount,
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+};
+
+/// configurable should be set before the bidder funds the predicate
+configurable {
+    FEE_AMOUNT: u64 = 0,
+    FEE_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the asset locked in the predicate as payment for the NFT
+    ASK_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// the payment for the NFT, the fee included when it is paid in `ASK_ASSET`
+    OFFER_AMOUNT: u64 = 0,
+    /// the bidder, who receives the NFT and may cancel the offer
+    RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    NFT_ASSET_ID: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// checks that `output_index` is a coin output paying `amount` of `asset` to `to`
+fn is_paid(output_index: u64, to: Address, asset: AssetId, amount: u64) -> bool {
+    match output_type(output_index) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+
+    match get_output_details(output_index) {
+        Some((output_to, output_asset, output_amount)) => output_to == to && output_asset == asset && output_amount == amount,
+        None => false,
+    }
+}
+
+/// total of `ASK_ASSET` spent from the offer, every input owned by the same predicate as the first one
+fn locked_amount() -> Option<u64> {
+    let offer = match input_coin_owner(0) {
+        Some(offer) => offer,
+        None => return None,
+    };
+
+    let inputs = input_count().as_u64();
+    let mut locked = 0;
+    let mut i = 0;
+    while i < inputs {
+        if input_coin_owner(i) == Some(offer) {
+            if input_asset_id(i) != Some(ASK_ASSET) {
+                return None;
+            }
+            locked += input_amount(i).unwrap_or(0);
+        }
+        i += 1;
+    }
+
+    Some(locked)
+}
+
+fn main() -> bool {
+    // Allow cancellation by the bidder if they provide an input coin, however many coins fund the offer
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == RECEIVER {
+                return true;
+            }
+        }
+        i += 1;
+    }
+
+    // validate input: the locked payment followed by the NFT being delivered
+    match (input_asset_id(0), input_asset_id(1)) {
+        (Some(ask_asset), Some(nft_asset)) => {
+            if ask_asset != ASK_ASSET || nft_asset != NFT_ASSET_ID {
+                return false;
+            }
+        },
+        _ => return false,
+    }
+
+    // The acceptor is paid `OFFER_AMOUNT` less the fee, whatever else the offer holds is refunded to the bidder
+    let locked = match locked_amount() {
+        Some(locked) => locked,
+        None => return false,
+    };
+    let fee_from_payment = if FEE_ASSET == ASK_ASSET { FEE_AMOUNT } else { 0 };
+    if locked < OFFER_AMOUNT || fee_from_payment > OFFER_AMOUNT {
+        return false;
+    }
+    let refund = locked - OFFER_AMOUNT;
+
+    // Validate output configuration
+    if output_count() < 3 || (refund > 0 && output_count() < 4) {
+        return false
+    }
+
+    // The payout to the acceptor follows the fee, then the refund when there is one
+    match output_type(2) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+    match get_output_details(2) {
+        Some((_, payout_asset, payout_amount)) => {
+            if payout_asset != ASK_ASSET || payout_amount + fee_from_payment != OFFER_AMOUNT {
+                return false;
+            }
+        },
+        None => return false,
+    }
+    if refund > 0 && !is_paid(3, RECEIVER, ASK_ASSET, refund) {
+        return false;
+    }
+
+    // Ensure both outputs are Coin type
+    match (output_type(0), output_type(1)) {
+        (Some(Output::Coin), Some(Output::Coin)) => (),
+        _ => return false,
+    };
+
+    let output1 = get_output_details(0);
+    let output2 = get_output_details(1);
+
+    // The NFT goes to the bidder and the fee to the treasury
+    match (output1, output2) {
+        (Some((to_bidder, nft_asset, nft_amount)), Some((to_treasury, fee_asset, fee_amount))) => {
+            to_bidder == RECEIVER && nft_asset == NFT_ASSET_ID && nft_amount == 1 && to_treasury == TREASURY_ADDRESS && fee_asset == FEE_ASSET && fee_amount == FEE_AMOUNT
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,151 @@
+predicate;
+
+use std::{
+    b512::B512,
+    ecr::ec_recover_address,
+    hash::sha256,
+    inputs::{
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+    tx::tx_expiration,
+};
+
+/// configurable should be set before we deploy predicate
+configurable {
+    /// the seller, who signs orders, receives the price and may spend anything the account holds
+    OWNER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// marketplace fee as basis points of the order price, paid in the ask asset
+    FEE_BPS: u64 = 0,
+}
+
+/// the largest valid `FEE_BPS`, a fee of the whole price
+const MAX_FEE_BPS: u64 = 10_000;
+
+/// a sale of one NFT held by the account, signed by the owner off-chain and valid until block `expiry`
+struct Order {
+    nft_asset_id: AssetId,
+    ask_asset: AssetId,
+    ask_amount: u64,
+    nonce: u64,
+    expiry: u32,
+}
+
+/// the canonical digest the owner signs
+fn order_hash(order: Order) -> b256 {
+    sha256((order.nft_asset_id, order.ask_asset, order.ask_amount, order.nonce, order.expiry.as_u64()))
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// checks that the output at `output_index` is a coin of `amount` of `asset` sent to `to`
+fn is_paid(output_index: u64, to: Address, asset: AssetId, amount: u64) -> bool {
+    match output_type(output_index) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+
+    match get_output_details(output_index) {
+        Some((output_to, output_asset, output_amount)) => output_to == to && output_asset == asset && output_amount == amount,
+        None => false,
+    }
+}
+
+/// `order` and `signature` are supplied by the taker, the signature must be the owner's over `order_hash`
+fn main(order: Order, signature: B512) -> bool {
+    // The owner spends the account freely if they provide an input coin
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == OWNER {
+                return true;
+            }
+        }
+        i += 1;
+    }
+
+    // The ordered NFT is the first input and the only coin of the account spent by the fill
+    let account = match input_coin_owner(0) {
+        Some(account) => account,
+        None => return false,
+    };
+    match input_asset_id(0) {
+        Some(nft_asset) => {
+            if nft_asset != order.nft_asset_id {
+                return false;
+            }
+        },
+        None => return false,
+    }
+    let mut i = 1;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == account {
+                return false;
+            }
+        }
+        i += 1;
+    }
+
+    // A predicate cannot read the block height, instead the transaction must expire no later than the order
+    match tx_expiration() {
+        Some(expiration) => {
+            if expiration > order.expiry {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    if FEE_BPS > MAX_FEE_BPS {
+        return false;
+    }
+
+    match ec_recover_address(signature, order_hash(order)) {
+        Ok(signer) => {
+            if signer != OWNER {
+                return false;
+            }
+        },
+        Err(_) => return false,
+    }
+
+    // Validate output configuration
+    if output_count() < 2 {
+        return false
+    }
+
+    // split the price so that the multiplication cannot overflow
+    let fee = (order.ask_amount / MAX_FEE_BPS) * FEE_BPS + (order.ask_amount % MAX_FEE_BPS) * FEE_BPS / MAX_FEE_BPS;
+
+    is_paid(0, OWNER, order.ask_asset, order.ask_amount) && is_paid(1, TREASURY_ADDRESS, order.ask_asset, fee)
+}
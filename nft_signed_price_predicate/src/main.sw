@@ -0,0 +1,146 @@
+predicate;
+
+use std::{
+    b512::B512,
+    ecr::ec_recover_address,
+    hash::sha256,
+    inputs::{
+        input_coin_owner,
+        input_count,
+        input_asset_id,
+    },
+    outputs::{
+        Output,
+        output_amount,
+        output_asset_id,
+        output_asset_to,
+        output_count,
+        output_type,
+    },
+    tx::tx_expiration,
+};
+
+/// configurable should be set before we deploy predicate
+configurable {
+    /// the seller, who signs price quotes, receives the price and may cancel the listing
+    RECEIVER: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    NFT_ASSET_ID: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    ASK_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// quotes with a lower nonce are rejected, raised when re-listing to invalidate older quotes
+    MIN_NONCE: u64 = 0,
+    FEE_AMOUNT: u64 = 0,
+    FEE_ASSET: AssetId = AssetId::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    TREASURY_ADDRESS: Address = Address::from(0x0000000000000000000000000000000000000000000000000000000000000000),
+    /// fee as basis points of the quoted price, when non zero it replaces `FEE_AMOUNT`
+    FEE_BPS: u64 = 0,
+}
+
+/// the largest valid `FEE_BPS`, a fee of the whole price
+const MAX_FEE_BPS: u64 = 10_000;
+
+/// a price the seller signed off-chain, valid until block `expiry`
+struct PriceQuote {
+    price: u64,
+    nonce: u64,
+    expiry: u32,
+}
+
+/// the digest the seller signs, binding the quote to the listed NFT and the ask asset
+fn quote_digest(quote: PriceQuote) -> b256 {
+    sha256((NFT_ASSET_ID, ASK_ASSET, quote.price, quote.nonce, quote.expiry.as_u64()))
+}
+
+/// extracts output details
+fn get_output_details(output_index: u64) -> Option<(Address, AssetId, u64)> {
+    let to = match output_asset_to(output_index) {
+        Some(address) => address,
+        None => return None,
+    };
+
+    let asset_id = match output_asset_id(output_index) {
+        Some(asset_id) => asset_id,
+        None => return None,
+    };
+
+    let amount = match output_amount(output_index) {
+        Some(amount) => amount,
+        None => return None,
+    };
+
+    Some((to, asset_id, amount))
+}
+
+/// checks that the output at `output_index` is a coin of `amount` of `asset` sent to `to`
+fn is_paid(output_index: u64, to: Address, asset: AssetId, amount: u64) -> bool {
+    match output_type(output_index) {
+        Some(Output::Coin) => (),
+        _ => return false,
+    };
+
+    match get_output_details(output_index) {
+        Some((output_to, output_asset, output_amount)) => output_to == to && output_asset == asset && output_amount == amount,
+        None => false,
+    }
+}
+
+/// `quote` and `signature` are supplied by the taker, the signature must be the seller's over `quote_digest`
+fn main(quote: PriceQuote, signature: B512) -> bool {
+    // Allow cancellation by receiver if they provide an input coin
+    let inputs = input_count().as_u64();
+    let mut i = 0;
+    while i < inputs {
+        if let Some(owner) = input_coin_owner(i) {
+            if owner == RECEIVER {
+                return true;
+            }
+        }
+        i += 1;
+    }
+
+    // validate input
+    match input_asset_id(0) {
+        Some(nft_asset) => {
+            if nft_asset != NFT_ASSET_ID {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    // A predicate cannot read the block height, instead the transaction must expire no later than the quote
+    match tx_expiration() {
+        Some(expiration) => {
+            if expiration > quote.expiry {
+                return false;
+            }
+        },
+        None => return false,
+    }
+
+    if quote.nonce < MIN_NONCE || FEE_BPS > MAX_FEE_BPS {
+        return false;
+    }
+
+    match ec_recover_address(signature, quote_digest(quote)) {
+        Ok(signer) => {
+            if signer != RECEIVER {
+                return false;
+            }
+        },
+        Err(_) => return false,
+    }
+
+    // Validate output configuration
+    if output_count() < 2 {
+        return false
+    }
+
+    let fee = if FEE_BPS == 0 {
+        FEE_AMOUNT
+    } else {
+        // split the price so that the multiplication cannot overflow
+        (quote.price / MAX_FEE_BPS) * FEE_BPS + (quote.price % MAX_FEE_BPS) * FEE_BPS / MAX_FEE_BPS
+    };
+
+    is_paid(0, RECEIVER, ASK_ASSET, quote.price) && is_paid(1, TREASURY_ADDRESS, FEE_ASSET, fee)
+}
@@ -0,0 +1,89 @@
+//! The NFT collection contract whose tokens are listed
+//!
+//! The contract id only depends on the bytecode, the salt and the initial storage, so the id of
+//! a collection and the asset ids of its tokens are known before it is deployed
+
+use fuels::{
+    prelude::{
+        abigen, Contract, LoadConfiguration, Result, StorageConfiguration, TxPolicies,
+        WalletUnlocked,
+    },
+    programs::contract::Regular,
+    tx::StorageSlot,
+    types::{AssetId, Bytes32, ContractId, Salt},
+};
+use sha2::{Digest, Sha256};
+
+abigen!(Contract(
+    name = "NFT",
+    abi = "../NFT-contract/out/debug/NFT-contract-abi.json"
+));
+
+/// The collection contract as built by `forc`, its storage slots are read from the same directory
+pub const NFT_CONTRACT_BINARY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../NFT-contract/out/debug/NFT-contract.bin"
+);
+
+/// Everything the contract id of a collection depends on besides its bytecode
+#[derive(Debug, Clone, Default)]
+pub struct CollectionConfig {
+    pub salt: Salt,
+    /// Initial storage replacing the slots `forc` generated, or added to them
+    pub storage_slots: Vec<StorageSlot>,
+}
+
+impl CollectionConfig {
+    /// A collection deployed with `salt`, a distinct salt gives a distinct contract id
+    pub fn new(salt: impl Into<Salt>) -> Self {
+        Self {
+            salt: salt.into(),
+            storage_slots: vec![],
+        }
+    }
+
+    pub fn with_storage_slots(mut self, storage_slots: Vec<StorageSlot>) -> Self {
+        self.storage_slots = storage_slots;
+        self
+    }
+
+    /// The contract this config deploys
+    pub fn contract(&self) -> Result<Contract<Regular>> {
+        let storage =
+            StorageConfiguration::default().add_slot_overrides(self.storage_slots.iter().cloned());
+
+        Contract::load_from(
+            NFT_CONTRACT_BINARY,
+            LoadConfiguration::default()
+                .with_salt(self.salt)
+                .with_storage_configuration(storage),
+        )
+    }
+
+    /// The id the contract will have once deployed
+    pub fn contract_id(&self) -> Result<ContractId> {
+        Ok(self.contract()?.contract_id())
+    }
+}
+
+/// Deploys a collection and returns its id with a handle calling it as `wallet`
+pub async fn deploy_collection(
+    wallet: &WalletUnlocked,
+    config: &CollectionConfig,
+) -> Result<(ContractId, NFT<WalletUnlocked>)> {
+    let contract_id = config
+        .contract()?
+        .deploy(wallet, TxPolicies::default())
+        .await?;
+    let instance = NFT::new(contract_id.clone(), wallet.clone());
+
+    Ok((contract_id.into(), instance))
+}
+
+/// The asset minted by `contract` for `sub_id`
+pub fn get_asset_id(sub_id: Bytes32, contract: ContractId) -> AssetId {
+    let mut hasher = Sha256::new();
+    hasher.update(*contract);
+    hasher.update(*sub_id);
+    AssetId::new(*Bytes32::from(<[u8; 32]>::from(hasher.finalize())))
+}
@@ -0,0 +1,219 @@
+//! Recovers the configurables of a fixed price listing from predicate bytecode seen on-chain
+//!
+//! The compiler places every configurable at a fixed offset of the bytecode, listed in the ABI
+//! JSON. Values are laid out as the SDK encodes them: integers as 8 byte big endian words and
+//! ids as their 32 bytes, arrays as their elements back to back
+
+use crate::listing::{ExtraFee, ListingParams, MAX_EXTRA_FEE_RECIPIENTS};
+use fuels::types::{input::Input, Address, AssetId};
+use serde_json::Value;
+use std::{collections::HashMap, fmt};
+
+/// The ABI of the current `nft_fixed_price_swap_predicate` release
+pub const FIXED_PRICE_PREDICATE_ABI: &str = include_str!(
+    "../../nft_fixed_price_swap_predicate/out/debug/nft_fixed_price_swap_predicate-abi.json"
+);
+
+/// Why configurables could not be decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The ABI JSON does not describe configurables as expected
+    InvalidAbi(String),
+    /// The ABI uses a configurable type the decoder cannot size
+    UnsupportedType(String),
+    /// The ABI has no configurable of this name
+    MissingConfigurable(&'static str),
+    /// The bytecode ends before the configurable of this name
+    Truncated(&'static str),
+    /// The configurable of this name holds a value the predicate cannot have been created with
+    InvalidValue(&'static str),
+    /// The input does not spend a predicate
+    NotAPredicate,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAbi(reason) => write!(f, "invalid predicate ABI: {reason}"),
+            Self::UnsupportedType(name) => write!(f, "unsupported configurable type `{name}`"),
+            Self::MissingConfigurable(name) => write!(f, "ABI has no configurable `{name}`"),
+            Self::Truncated(name) => write!(f, "bytecode ends before configurable `{name}`"),
+            Self::InvalidValue(name) => write!(f, "invalid value of configurable `{name}`"),
+            Self::NotAPredicate => write!(f, "input does not spend a predicate"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Where each configurable lives in the bytecode and how many bytes it takes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigurableLayout {
+    slots: HashMap<String, (usize, usize)>,
+}
+
+impl ConfigurableLayout {
+    /// Reads the configurable offsets and types from a predicate ABI JSON
+    pub fn from_abi_json(abi: &str) -> Result<Self, DecodeError> {
+        let abi: Value =
+            serde_json::from_str(abi).map_err(|e| DecodeError::InvalidAbi(e.to_string()))?;
+
+        let mut types = HashMap::new();
+        for concrete_type in array(&abi, "concreteTypes")? {
+            types.insert(
+                string(concrete_type, "concreteTypeId")?,
+                string(concrete_type, "type")?,
+            );
+        }
+
+        let mut slots = HashMap::new();
+        for configurable in array(&abi, "configurables")? {
+            let type_id = string(configurable, "concreteTypeId")?;
+            let type_name = types
+                .get(type_id)
+                .ok_or_else(|| DecodeError::InvalidAbi(format!("unknown type id {type_id}")))?;
+            let offset = configurable["offset"]
+                .as_u64()
+                .ok_or_else(|| DecodeError::InvalidAbi("configurable without offset".into()))?;
+
+            slots.insert(
+                string(configurable, "name")?.to_string(),
+                (offset as usize, type_size(type_name)?),
+            );
+        }
+
+        Ok(Self { slots })
+    }
+
+    /// The layout of the current `nft_fixed_price_swap_predicate` release
+    pub fn fixed_price_predicate() -> Self {
+        Self::from_abi_json(FIXED_PRICE_PREDICATE_ABI).expect("the bundled ABI is valid")
+    }
+
+    /// The bytes of the configurable `name` within `code`
+    fn read<'a>(&self, code: &'a [u8], name: &'static str) -> Result<&'a [u8], DecodeError> {
+        let (offset, size) = *self
+            .slots
+            .get(name)
+            .ok_or(DecodeError::MissingConfigurable(name))?;
+
+        code.get(offset..offset + size)
+            .ok_or(DecodeError::Truncated(name))
+    }
+
+    fn u64(&self, code: &[u8], name: &'static str) -> Result<u64, DecodeError> {
+        let word = self.read(code, name)?;
+        let word = word
+            .try_into()
+            .map_err(|_| DecodeError::InvalidValue(name))?;
+
+        Ok(u64::from_be_bytes(word))
+    }
+
+    fn u64s(&self, code: &[u8], name: &'static str) -> Result<Vec<u64>, DecodeError> {
+        Ok(self
+            .read(code, name)?
+            .chunks_exact(8)
+            .map(|word| u64::from_be_bytes(word.try_into().expect("chunks of 8 bytes")))
+            .collect())
+    }
+
+    fn ids<T: From<[u8; 32]>>(
+        &self,
+        code: &[u8],
+        name: &'static str,
+    ) -> Result<Vec<T>, DecodeError> {
+        Ok(self
+            .read(code, name)?
+            .chunks_exact(32)
+            .map(|id| T::from(id.try_into().expect("chunks of 32 bytes")))
+            .collect())
+    }
+
+    fn id<T: From<[u8; 32]>>(&self, code: &[u8], name: &'static str) -> Result<T, DecodeError> {
+        self.ids(code, name)?
+            .pop()
+            .ok_or(DecodeError::InvalidValue(name))
+    }
+
+    /// Decodes the listing a fixed price predicate was created for from its bytecode
+    pub fn decode_listing(&self, code: &[u8]) -> Result<ListingParams, DecodeError> {
+        let mut listing = ListingParams::new(
+            self.id::<AssetId>(code, "NFT_ASSET_ID")?,
+            self.id::<Address>(code, "RECEIVER")?,
+        );
+        listing.ask_asset = self.id(code, "ASK_ASSET")?;
+        listing.ask_amount = self.u64(code, "ASK_AMOUNT")?;
+        listing.fee_asset = self.id(code, "FEE_ASSET")?;
+        listing.treasury = self.id(code, "TREASURY_ADDRESS")?;
+        listing.fee_amount = self.u64(code, "FEE_AMOUNT")?;
+        listing.fee_bps = self.u64(code, "FEE_BPS")?;
+        listing.max_referral_bps = self.u64(code, "MAX_REFERRAL_BPS")?;
+
+        let extra_fee_count = self.u64(code, "EXTRA_FEE_RECIPIENT_COUNT")? as usize;
+        if extra_fee_count > MAX_EXTRA_FEE_RECIPIENTS {
+            return Err(DecodeError::InvalidValue("EXTRA_FEE_RECIPIENT_COUNT"));
+        }
+        let recipients = self.ids::<Address>(code, "EXTRA_FEE_RECIPIENTS")?;
+        let amounts = self.u64s(code, "EXTRA_FEE_AMOUNTS")?;
+        let bps = self.u64s(code, "EXTRA_FEE_BPS")?;
+        listing.extra_fees = recipients
+            .into_iter()
+            .zip(amounts)
+            .zip(bps)
+            .take(extra_fee_count)
+            .map(|((recipient, amount), bps)| ExtraFee {
+                recipient,
+                amount,
+                bps,
+            })
+            .collect();
+
+        Ok(listing)
+    }
+
+    /// Decodes the listing of the predicate spent by `input`
+    pub fn decode_input(&self, input: &Input) -> Result<ListingParams, DecodeError> {
+        match input {
+            Input::ResourcePredicate { code, .. } => self.decode_listing(code),
+            _ => Err(DecodeError::NotAPredicate),
+        }
+    }
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>, DecodeError> {
+    value[key]
+        .as_array()
+        .ok_or_else(|| DecodeError::InvalidAbi(format!("missing `{key}`")))
+}
+
+fn string<'a>(value: &'a Value, key: &str) -> Result<&'a str, DecodeError> {
+    value[key]
+        .as_str()
+        .ok_or_else(|| DecodeError::InvalidAbi(format!("missing `{key}`")))
+}
+
+/// The encoded size of a configurable of ABI type `type_name`
+fn type_size(type_name: &str) -> Result<usize, DecodeError> {
+    match type_name {
+        "u64" => Ok(8),
+        "b256"
+        | "struct std::address::Address"
+        | "struct std::asset_id::AssetId"
+        | "struct std::contract_id::ContractId" => Ok(32),
+        _ => {
+            // Arrays are named `[<element>; <length>]`
+            let (element, length) = type_name
+                .strip_prefix('[')
+                .and_then(|inner| inner.strip_suffix(']'))
+                .and_then(|inner| inner.rsplit_once(';'))
+                .ok_or_else(|| DecodeError::UnsupportedType(type_name.to_string()))?;
+            let length: usize = length
+                .trim()
+                .parse()
+                .map_err(|_| DecodeError::UnsupportedType(type_name.to_string()))?;
+
+            Ok(type_size(element.trim())? * length)
+        }
+    }
+}
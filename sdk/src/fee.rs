@@ -0,0 +1,143 @@
+//! The marketplace fee of a listing, computed exactly as the fixed price predicate computes it
+
+use crate::{listing::MAX_EXTRA_FEE_RECIPIENTS, predicate::MAX_FEE_BPS};
+use fuels::types::{output::Output, Address, AssetId};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How the fee owed to one recipient is determined
+///
+/// `fee` must compute exactly what the predicate's `expected_fee` computes on-chain, otherwise
+/// listings created with this policy cannot be filled. Written as `{ fixed = 10 }` or
+/// `{ bps = 250 }` in config files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePolicy {
+    /// An absolute amount, set as `FEE_AMOUNT`
+    Fixed(u64),
+    /// Basis points of the price rounded down, set as `FEE_BPS`
+    Bps(u64),
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+impl FeePolicy {
+    /// Panics when `bps` exceeds `MAX_FEE_BPS`, the predicate rejects such listings
+    pub fn bps(bps: u64) -> Self {
+        assert!(bps <= MAX_FEE_BPS, "fee of {bps} bps exceeds {MAX_FEE_BPS}");
+        Self::Bps(bps)
+    }
+
+    /// The policy of a fee amount and bps configurable pair, bps win when non zero
+    pub fn from_configurables(amount: u64, bps: u64) -> Self {
+        match bps {
+            0 => Self::Fixed(amount),
+            bps => Self::Bps(bps),
+        }
+    }
+
+    /// The fee amount and bps configurables of this policy
+    pub fn configurables(&self) -> (u64, u64) {
+        match *self {
+            Self::Fixed(amount) => (amount, 0),
+            Self::Bps(bps) => (0, bps),
+        }
+    }
+
+    /// The fee owed on a sale at `price`
+    pub fn fee(&self, price: u64) -> u64 {
+        match *self {
+            Self::Fixed(amount) => amount,
+            // Same split as the predicate so that the multiplication cannot overflow
+            Self::Bps(bps) => {
+                (price / MAX_FEE_BPS) * bps + (price % MAX_FEE_BPS) * bps / MAX_FEE_BPS
+            }
+        }
+    }
+}
+
+/// The most fee recipients a listing can have, the treasury plus the extra recipients
+pub const MAX_FEE_RECIPIENTS: usize = MAX_EXTRA_FEE_RECIPIENTS + 1;
+
+/// Why a fee split cannot be built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeError {
+    /// The split would pay more than `MAX_FEE_RECIPIENTS` recipients
+    TooManyRecipients(usize),
+}
+
+impl fmt::Display for FeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyRecipients(count) => write!(
+                f,
+                "fee split has {count} recipients, a listing pays at most {MAX_FEE_RECIPIENTS}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FeeError {}
+
+/// How the marketplace fee of a listing is split between recipients
+///
+/// The first recipient is the treasury, every recipient is paid its own fee in order by the
+/// outputs following the payment to the receiver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSplit {
+    recipients: Vec<(Address, FeePolicy)>,
+}
+
+impl FeeSplit {
+    /// A split paying everything to the treasury
+    pub fn new(treasury: Address, fee: FeePolicy) -> Self {
+        Self {
+            recipients: vec![(treasury, fee)],
+        }
+    }
+
+    /// Adds a recipient paid after the ones already in the split
+    ///
+    /// Fails when the split already holds `MAX_FEE_RECIPIENTS` recipients
+    pub fn with_recipient(mut self, recipient: Address, fee: FeePolicy) -> Result<Self, FeeError> {
+        if self.recipients.len() >= MAX_FEE_RECIPIENTS {
+            return Err(FeeError::TooManyRecipients(self.recipients.len() + 1));
+        }
+        self.recipients.push((recipient, fee));
+        Ok(self)
+    }
+
+    /// Every recipient and its policy, the treasury first
+    pub fn recipients(&self) -> &[(Address, FeePolicy)] {
+        &self.recipients
+    }
+
+    /// The fee owed to every recipient, in output order, on a sale at `price`
+    pub fn amounts(&self, price: u64) -> Vec<(Address, u64)> {
+        self.recipients
+            .iter()
+            .map(|(recipient, fee)| (*recipient, fee.fee(price)))
+            .collect()
+    }
+
+    /// The fee owed to all recipients together on a sale at `price`
+    pub fn total(&self, price: u64) -> u64 {
+        self.recipients.iter().map(|(_, fee)| fee.fee(price)).sum()
+    }
+}
+
+/// Coin outputs paying each `(recipient, amount)` in `fee_asset`, in the given order
+pub fn fee_outputs(amounts: &[(Address, u64)], fee_asset: AssetId) -> Vec<Output> {
+    amounts
+        .iter()
+        .map(|(recipient, amount)| Output::Coin {
+            to: *recipient,
+            amount: *amount,
+            asset_id: fee_asset,
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+//! Types shared by everything that creates, fills or indexes nullstate marketplace listings
+
+pub mod collection;
+pub mod decode;
+pub mod fee;
+pub mod listing;
+pub mod offer;
+pub mod order;
+pub mod predicate;
+pub mod serde_hex;
+pub mod transactions;
@@ -0,0 +1,143 @@
+//! The parameters of a fixed price listing, one field per predicate configurable
+
+use crate::{
+    fee::{FeeError, FeePolicy, FeeSplit},
+    serde_hex,
+};
+use fuels::types::{Address, AssetId};
+use serde::{Deserialize, Serialize};
+
+/// The most fee recipients paid after the treasury, the size of the `EXTRA_FEE_*` configurables
+pub const MAX_EXTRA_FEE_RECIPIENTS: usize = 3;
+
+/// A fee recipient paid after the treasury
+///
+/// Like the treasury fee the recipient is owed `bps` of the price when `bps` is non zero and
+/// `amount` otherwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraFee {
+    #[serde(with = "serde_hex")]
+    pub recipient: Address,
+    pub amount: u64,
+    pub bps: u64,
+}
+
+/// Every configurable of a `nft_fixed_price_swap_predicate` listing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListingParams {
+    #[serde(with = "serde_hex")]
+    pub nft_asset_id: AssetId,
+    #[serde(with = "serde_hex")]
+    pub receiver: Address,
+    #[serde(with = "serde_hex")]
+    pub ask_asset: AssetId,
+    pub ask_amount: u64,
+    #[serde(with = "serde_hex")]
+    pub fee_asset: AssetId,
+    #[serde(with = "serde_hex")]
+    pub treasury: Address,
+    pub fee_amount: u64,
+    pub fee_bps: u64,
+    /// At most `MAX_EXTRA_FEE_RECIPIENTS`, paid in order after the treasury
+    pub extra_fees: Vec<ExtraFee>,
+    pub max_referral_bps: u64,
+}
+
+impl ListingParams {
+    /// Creates a listing of `nft_asset_id` by `receiver` with no price or fee set
+    pub fn new(nft_asset_id: AssetId, receiver: Address) -> Self {
+        Self {
+            nft_asset_id,
+            receiver,
+            ask_asset: AssetId::zeroed(),
+            ask_amount: 0,
+            fee_asset: AssetId::zeroed(),
+            treasury: Address::zeroed(),
+            fee_amount: 0,
+            fee_bps: 0,
+            extra_fees: vec![],
+            max_referral_bps: 0,
+        }
+    }
+
+    pub fn with_price(mut self, ask_asset: AssetId, ask_amount: u64) -> Self {
+        self.ask_asset = ask_asset;
+        self.ask_amount = ask_amount;
+        self
+    }
+
+    /// Pays the whole fee to `treasury`
+    pub fn with_fee(self, fee_asset: AssetId, fee: FeePolicy, treasury: Address) -> Self {
+        self.with_fee_split(fee_asset, FeeSplit::new(treasury, fee))
+    }
+
+    /// Sets the treasury and extra fee configurables from `fees`
+    pub fn with_fee_split(mut self, fee_asset: AssetId, fees: FeeSplit) -> Self {
+        let (treasury, treasury_fee) = fees.recipients()[0];
+        self.fee_asset = fee_asset;
+        self.treasury = treasury;
+        (self.fee_amount, self.fee_bps) = treasury_fee.configurables();
+        self.extra_fees = fees.recipients()[1..]
+            .iter()
+            .map(|(recipient, fee)| {
+                let (amount, bps) = fee.configurables();
+                ExtraFee {
+                    recipient: *recipient,
+                    amount,
+                    bps,
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Lets a frontend charge the taker up to `max_referral_bps` of the price as a referral when
+    /// filling, on top of the price
+    pub fn with_max_referral_bps(mut self, max_referral_bps: u64) -> Self {
+        self.max_referral_bps = max_referral_bps;
+        self
+    }
+
+    /// Every fee recipient of the listing and its policy, the treasury first
+    fn fee_policies(&self) -> impl Iterator<Item = (Address, FeePolicy)> + '_ {
+        let treasury_fee = FeePolicy::from_configurables(self.fee_amount, self.fee_bps);
+        std::iter::once((self.treasury, treasury_fee)).chain(self.extra_fees.iter().map(
+            |extra_fee| {
+                (
+                    extra_fee.recipient,
+                    FeePolicy::from_configurables(extra_fee.amount, extra_fee.bps),
+                )
+            },
+        ))
+    }
+
+    /// The fee recipients of the listing, the treasury first
+    ///
+    /// Fails when the listing has more than `MAX_EXTRA_FEE_RECIPIENTS` extra fee recipients
+    pub fn fee_split(&self) -> Result<FeeSplit, FeeError> {
+        let treasury_fee = FeePolicy::from_configurables(self.fee_amount, self.fee_bps);
+        self.extra_fees.iter().try_fold(
+            FeeSplit::new(self.treasury, treasury_fee),
+            |split, extra_fee| {
+                split.with_recipient(
+                    extra_fee.recipient,
+                    FeePolicy::from_configurables(extra_fee.amount, extra_fee.bps),
+                )
+            },
+        )
+    }
+
+    /// The fee owed to every recipient, in output order, when the listing is filled
+    pub fn fee_amounts(&self) -> Vec<(Address, u64)> {
+        self.fee_policies()
+            .map(|(recipient, fee)| (recipient, fee.fee(self.ask_amount)))
+            .collect()
+    }
+
+    /// The fee all recipients together must be paid when the listing is filled
+    pub fn total_fee(&self) -> u64 {
+        self.fee_policies()
+            .map(|(_, fee)| fee.fee(self.ask_amount))
+            .sum()
+    }
+}
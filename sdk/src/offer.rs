@@ -0,0 +1,275 @@
+//! Buyer offers, predicates locking a payment until an NFT is delivered to the bidder
+//!
+//! An offer names either a single NFT or a whole collection. The predicate address only depends
+//! on the parameters, so an offer file needs no claimed address to be checked against
+
+use crate::serde_hex;
+use fuels::{
+    accounts::predicate::Predicate,
+    prelude::abigen,
+    types::{Address, AssetId, Bits256, Bytes32, ContractId},
+};
+use serde::{Deserialize, Serialize};
+
+abigen!(
+    Predicate(
+        name = "OfferPredicate",
+        abi = "../nft_offer_predicate/out/debug/nft_offer_predicate-abi.json"
+    ),
+    Predicate(
+        name = "CollectionOfferPredicate",
+        abi = "../nft_collection_offer_predicate/out/debug/nft_collection_offer_predicate-abi.json"
+    )
+);
+
+/// The bytecode of the current `nft_offer_predicate` release, before configurables
+pub const OFFER_PREDICATE_CODE: &[u8] =
+    include_bytes!("../../nft_offer_predicate/out/debug/nft_offer_predicate.bin");
+
+/// The bytecode of the current `nft_collection_offer_predicate` release, before configurables
+pub const COLLECTION_OFFER_PREDICATE_CODE: &[u8] = include_bytes!(
+    "../../nft_collection_offer_predicate/out/debug/nft_collection_offer_predicate.bin"
+);
+
+/// Describes a buyer's offer for a single NFT
+///
+/// The bidder locks `amount` of `ask_asset` in the predicate built from these parameters. Whoever
+/// delivers `nft_asset_id` to the bidder and pays `fee_amount` to the treasury is paid the rest of
+/// `amount`, anything locked beyond it goes back to the bidder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OfferParams {
+    #[serde(with = "serde_hex")]
+    pub nft_asset_id: AssetId,
+    /// The bidder
+    #[serde(with = "serde_hex")]
+    pub receiver: Address,
+    #[serde(with = "serde_hex")]
+    pub ask_asset: AssetId,
+    pub amount: u64,
+    #[serde(with = "serde_hex")]
+    pub fee_asset: AssetId,
+    pub fee_amount: u64,
+    #[serde(with = "serde_hex")]
+    pub treasury: Address,
+}
+
+impl OfferParams {
+    /// Creates an offer from `receiver` for `nft_asset_id` with no payment or fee set
+    pub fn new(nft_asset_id: AssetId, receiver: Address) -> Self {
+        Self {
+            nft_asset_id,
+            receiver,
+            ask_asset: AssetId::zeroed(),
+            amount: 0,
+            fee_asset: AssetId::zeroed(),
+            fee_amount: 0,
+            treasury: Address::zeroed(),
+        }
+    }
+
+    pub fn with_payment(mut self, ask_asset: AssetId, amount: u64) -> Self {
+        self.ask_asset = ask_asset;
+        self.amount = amount;
+        self
+    }
+
+    pub fn with_fee(mut self, fee_asset: AssetId, fee_amount: u64, treasury: Address) -> Self {
+        self.fee_asset = fee_asset;
+        self.fee_amount = fee_amount;
+        self.treasury = treasury;
+        self
+    }
+
+    /// The amount the acceptor keeps after paying the treasury
+    pub fn proceeds(&self) -> u64 {
+        if self.fee_asset == self.ask_asset {
+            self.amount - self.fee_amount
+        } else {
+            self.amount
+        }
+    }
+
+    pub fn configurables(&self) -> OfferPredicateConfigurables {
+        OfferPredicateConfigurables::default()
+            .with_FEE_AMOUNT(self.fee_amount)
+            .unwrap()
+            .with_FEE_ASSET(self.fee_asset)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_ASK_ASSET(self.ask_asset)
+            .unwrap()
+            .with_OFFER_AMOUNT(self.amount)
+            .unwrap()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_ASSET_ID(self.nft_asset_id)
+            .unwrap()
+    }
+
+    /// The predicate the bidder funds
+    pub fn predicate(&self) -> Predicate {
+        Predicate::from_code(OFFER_PREDICATE_CODE.to_vec()).with_configurables(self.configurables())
+    }
+}
+
+/// Describes a buyer's offer for any NFT minted by `nft_contract_id`
+///
+/// The bidder locks `amount` of `ask_asset` in the predicate built from these parameters. Whoever
+/// delivers a token of the collection to the bidder and pays `fee_amount` to the treasury may
+/// take the rest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionOfferParams {
+    #[serde(with = "serde_hex")]
+    pub nft_contract_id: ContractId,
+    /// The bidder
+    #[serde(with = "serde_hex")]
+    pub receiver: Address,
+    #[serde(with = "serde_hex")]
+    pub ask_asset: AssetId,
+    /// What the bidder locks, the predicate itself pays out whatever it holds
+    pub amount: u64,
+    #[serde(with = "serde_hex")]
+    pub fee_asset: AssetId,
+    pub fee_amount: u64,
+    #[serde(with = "serde_hex")]
+    pub treasury: Address,
+}
+
+impl CollectionOfferParams {
+    /// Creates an offer from `receiver` for any token of `nft_contract_id` with no payment or fee set
+    pub fn new(nft_contract_id: ContractId, receiver: Address) -> Self {
+        Self {
+            nft_contract_id,
+            receiver,
+            ask_asset: AssetId::zeroed(),
+            amount: 0,
+            fee_asset: AssetId::zeroed(),
+            fee_amount: 0,
+            treasury: Address::zeroed(),
+        }
+    }
+
+    pub fn with_payment(mut self, ask_asset: AssetId, amount: u64) -> Self {
+        self.ask_asset = ask_asset;
+        self.amount = amount;
+        self
+    }
+
+    pub fn with_fee(mut self, fee_asset: AssetId, fee_amount: u64, treasury: Address) -> Self {
+        self.fee_asset = fee_asset;
+        self.fee_amount = fee_amount;
+        self.treasury = treasury;
+        self
+    }
+
+    /// The amount the acceptor keeps after paying the treasury
+    pub fn proceeds(&self) -> u64 {
+        if self.fee_asset == self.ask_asset {
+            self.amount - self.fee_amount
+        } else {
+            self.amount
+        }
+    }
+
+    pub fn configurables(&self) -> CollectionOfferPredicateConfigurables {
+        CollectionOfferPredicateConfigurables::default()
+            .with_FEE_AMOUNT(self.fee_amount)
+            .unwrap()
+            .with_FEE_ASSET(self.fee_asset)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_ASK_ASSET(self.ask_asset)
+            .unwrap()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_CONTRACT_ID(self.nft_contract_id)
+            .unwrap()
+    }
+
+    /// The predicate the bidder funds, its address does not depend on the sub id
+    pub fn predicate(&self) -> Predicate {
+        self.predicate_for_sub_id(Bytes32::zeroed())
+    }
+
+    /// The predicate with `sub_id` supplied as predicate data, used to accept the offer
+    pub fn predicate_for_sub_id(&self, sub_id: Bytes32) -> Predicate {
+        let predicate_data = CollectionOfferPredicateEncoder::default()
+            .encode_data(Bits256(*sub_id))
+            .unwrap();
+
+        Predicate::from_code(COLLECTION_OFFER_PREDICATE_CODE.to_vec())
+            .with_configurables(self.configurables())
+            .with_data(predicate_data)
+    }
+}
+
+/// An offer of either kind, as exchanged in offer files and stored by the indexer
+///
+/// Written as the parameters of the offer plus `"kind": "nft"` or `"kind": "collection"`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Offer {
+    Nft(OfferParams),
+    Collection(CollectionOfferParams),
+}
+
+impl Offer {
+    /// Reads an offer file holding either a single offer or a JSON array of offers
+    pub fn many_from_json(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        if !json.trim_start().starts_with('[') {
+            return Ok(vec![serde_json::from_str(json)?]);
+        }
+
+        serde_json::from_str(json)
+    }
+
+    /// The address the payment is locked at
+    pub fn predicate_address(&self) -> Address {
+        match self {
+            Self::Nft(offer) => offer.predicate().address().into(),
+            Self::Collection(offer) => offer.predicate().address().into(),
+        }
+    }
+
+    /// The bidder
+    pub fn receiver(&self) -> Address {
+        match self {
+            Self::Nft(offer) => offer.receiver,
+            Self::Collection(offer) => offer.receiver,
+        }
+    }
+
+    pub fn ask_asset(&self) -> AssetId {
+        match self {
+            Self::Nft(offer) => offer.ask_asset,
+            Self::Collection(offer) => offer.ask_asset,
+        }
+    }
+
+    pub fn amount(&self) -> u64 {
+        match self {
+            Self::Nft(offer) => offer.amount,
+            Self::Collection(offer) => offer.amount,
+        }
+    }
+
+    /// Whether delivering `nft_asset_id`, a token of `contract_id` when known, accepts the offer
+    pub fn accepts(&self, nft_asset_id: AssetId, contract_id: Option<ContractId>) -> bool {
+        match self {
+            Self::Nft(offer) => offer.nft_asset_id == nft_asset_id,
+            Self::Collection(offer) => contract_id == Some(offer.nft_contract_id),
+        }
+    }
+
+    /// Whether `locked` of the ask asset held by the predicate still pays the offer in full
+    ///
+    /// A single NFT offer pays exactly its amount, a collection offer whatever it holds
+    pub fn is_open(&self, locked: u64) -> bool {
+        match self {
+            Self::Nft(offer) => locked > 0 && locked >= offer.amount,
+            Self::Collection(_) => locked > 0,
+        }
+    }
+}
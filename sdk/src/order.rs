@@ -0,0 +1,273 @@
+//! The canonical artifact describing a listing, exchanged between the API, the indexer and the CLI
+//!
+//! An order is encoded either as JSON, for humans and HTTP APIs, or in a compact binary layout.
+//! The order hash is the sha256 of the binary encoding, so it does not depend on JSON formatting
+
+use crate::{
+    listing::{ExtraFee, ListingParams, MAX_EXTRA_FEE_RECIPIENTS},
+    serde_hex,
+};
+use fuels::types::{Address, Bytes32};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// The order format produced by this version of the SDK
+pub const ORDER_VERSION: u8 = 1;
+
+/// When and where an order was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderMetadata {
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+    /// The latest block when the order was created
+    pub block_height: u32,
+    /// Unix timestamp in seconds after which frontends stop showing the order
+    ///
+    /// The fixed price predicate does not enforce it, the NFT stays fillable until the receiver
+    /// cancels the listing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// A listing together with everything needed to check and fill it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Order {
+    pub version: u8,
+    pub chain_id: u64,
+    /// sha256 of the predicate bytecode before the configurables are set
+    #[serde(with = "serde_hex")]
+    pub predicate_bytecode_hash: Bytes32,
+    /// The address the NFT is locked at, as claimed by the order creator
+    #[serde(with = "serde_hex")]
+    pub predicate_address: Address,
+    pub listing: ListingParams,
+    pub metadata: OrderMetadata,
+}
+
+/// Why bytes or JSON could not be read as an order
+#[derive(Debug)]
+pub enum OrderError {
+    /// The order was written by a newer or unknown version of the format
+    UnsupportedVersion(u8),
+    /// The binary encoding ended early
+    Truncated,
+    /// The binary encoding continues after the order
+    TrailingBytes(usize),
+    /// More than `MAX_EXTRA_FEE_RECIPIENTS` extra fee recipients
+    TooManyFeeRecipients(usize),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(f, "unsupported order version {version}"),
+            Self::Truncated => write!(f, "order encoding is truncated"),
+            Self::TrailingBytes(count) => write!(f, "{count} bytes follow the encoded order"),
+            Self::TooManyFeeRecipients(count) => write!(
+                f,
+                "order has {count} extra fee recipients, at most {MAX_EXTRA_FEE_RECIPIENTS} are supported"
+            ),
+            Self::Json(e) => write!(f, "invalid order JSON: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+impl Order {
+    /// Creates an order in the current format
+    ///
+    /// Panics when the listing has more than `MAX_EXTRA_FEE_RECIPIENTS` extra fee recipients
+    pub fn new(
+        chain_id: u64,
+        predicate_bytecode_hash: Bytes32,
+        predicate_address: Address,
+        listing: ListingParams,
+        metadata: OrderMetadata,
+    ) -> Self {
+        assert!(
+            listing.extra_fees.len() <= MAX_EXTRA_FEE_RECIPIENTS,
+            "a listing pays at most {MAX_EXTRA_FEE_RECIPIENTS} extra fee recipients"
+        );
+
+        Self {
+            version: ORDER_VERSION,
+            chain_id,
+            predicate_bytecode_hash,
+            predicate_address,
+            listing,
+            metadata,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("orders always serialize to JSON")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, OrderError> {
+        let order: Self = serde_json::from_str(json).map_err(OrderError::Json)?;
+        order.check()?;
+        Ok(order)
+    }
+
+    /// Reads an order file holding either a single order or a JSON array of orders
+    ///
+    /// The first character of the file tells which, so a malformed file reports the error of
+    /// the form it is written in
+    pub fn many_from_json(json: &str) -> Result<Vec<Self>, OrderError> {
+        if !json.trim_start().starts_with('[') {
+            return Ok(vec![Self::from_json(json)?]);
+        }
+
+        let orders: Vec<Self> = serde_json::from_str(json).map_err(OrderError::Json)?;
+        for order in &orders {
+            order.check()?;
+        }
+        Ok(orders)
+    }
+
+    /// The compact binary encoding
+    ///
+    /// Integers are big endian and ids raw 32 bytes, in the order of the fields of `Order` and
+    /// `ListingParams`. The extra fee recipients are preceded by their count as a single byte.
+    /// The expiry is only appended when set, so orders without one keep their hash
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let listing = &self.listing;
+        let mut bytes = Vec::with_capacity(350);
+
+        bytes.push(self.version);
+        bytes.extend_from_slice(&self.chain_id.to_be_bytes());
+        bytes.extend_from_slice(&*self.predicate_bytecode_hash);
+        bytes.extend_from_slice(&*self.predicate_address);
+
+        bytes.extend_from_slice(&*listing.nft_asset_id);
+        bytes.extend_from_slice(&*listing.receiver);
+        bytes.extend_from_slice(&*listing.ask_asset);
+        bytes.extend_from_slice(&listing.ask_amount.to_be_bytes());
+        bytes.extend_from_slice(&*listing.fee_asset);
+        bytes.extend_from_slice(&*listing.treasury);
+        bytes.extend_from_slice(&listing.fee_amount.to_be_bytes());
+        bytes.extend_from_slice(&listing.fee_bps.to_be_bytes());
+        bytes.push(listing.extra_fees.len() as u8);
+        for extra_fee in &listing.extra_fees {
+            bytes.extend_from_slice(&*extra_fee.recipient);
+            bytes.extend_from_slice(&extra_fee.amount.to_be_bytes());
+            bytes.extend_from_slice(&extra_fee.bps.to_be_bytes());
+        }
+        bytes.extend_from_slice(&listing.max_referral_bps.to_be_bytes());
+
+        bytes.extend_from_slice(&self.metadata.created_at.to_be_bytes());
+        bytes.extend_from_slice(&self.metadata.block_height.to_be_bytes());
+        if let Some(expires_at) = self.metadata.expires_at {
+            bytes.extend_from_slice(&expires_at.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, OrderError> {
+        let mut reader = Reader { bytes };
+
+        let version = reader.u8()?;
+        if version != ORDER_VERSION {
+            return Err(OrderError::UnsupportedVersion(version));
+        }
+        let chain_id = reader.u64()?;
+        let predicate_bytecode_hash = reader.id()?;
+        let predicate_address = reader.id()?;
+
+        let mut listing = ListingParams::new(reader.id()?, reader.id()?);
+        listing.ask_asset = reader.id()?;
+        listing.ask_amount = reader.u64()?;
+        listing.fee_asset = reader.id()?;
+        listing.treasury = reader.id()?;
+        listing.fee_amount = reader.u64()?;
+        listing.fee_bps = reader.u64()?;
+        let extra_fee_count = reader.u8()? as usize;
+        if extra_fee_count > MAX_EXTRA_FEE_RECIPIENTS {
+            return Err(OrderError::TooManyFeeRecipients(extra_fee_count));
+        }
+        for _ in 0..extra_fee_count {
+            listing.extra_fees.push(ExtraFee {
+                recipient: reader.id()?,
+                amount: reader.u64()?,
+                bps: reader.u64()?,
+            });
+        }
+        listing.max_referral_bps = reader.u64()?;
+
+        let mut metadata = OrderMetadata {
+            created_at: reader.u64()?,
+            block_height: reader.u32()?,
+            expires_at: None,
+        };
+        if reader.bytes.len() == 8 {
+            metadata.expires_at = Some(reader.u64()?);
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(OrderError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Ok(Self {
+            version,
+            chain_id,
+            predicate_bytecode_hash,
+            predicate_address,
+            listing,
+            metadata,
+        })
+    }
+
+    /// The stable identifier of the order, the sha256 of its binary encoding
+    pub fn hash(&self) -> Bytes32 {
+        Bytes32::from(<[u8; 32]>::from(Sha256::digest(self.to_bytes())))
+    }
+
+    /// Rejects orders the binary encoding cannot represent
+    fn check(&self) -> Result<(), OrderError> {
+        if self.version != ORDER_VERSION {
+            return Err(OrderError::UnsupportedVersion(self.version));
+        }
+        if self.listing.extra_fees.len() > MAX_EXTRA_FEE_RECIPIENTS {
+            return Err(OrderError::TooManyFeeRecipients(
+                self.listing.extra_fees.len(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Reads the binary encoding front to back
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], OrderError> {
+        if self.bytes.len() < N {
+            return Err(OrderError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().expect("split at N"))
+    }
+
+    fn u8(&mut self) -> Result<u8, OrderError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, OrderError> {
+        Ok(u32::from_be_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, OrderError> {
+        Ok(u64::from_be_bytes(self.take()?))
+    }
+
+    fn id<T: From<[u8; 32]>>(&mut self) -> Result<T, OrderError> {
+        Ok(T::from(self.take::<32>()?))
+    }
+}
@@ -0,0 +1,216 @@
+//! The fixed price swap predicate and verification of orders claiming to lock an NFT in it
+//!
+//! A buyer handed an order only trusts the predicate address once it is rebuilt here from a
+//! bytecode release this SDK knows, with the configurables of the order applied
+
+use crate::{
+    listing::{ListingParams, MAX_EXTRA_FEE_RECIPIENTS},
+    order::{Order, OrderMetadata},
+};
+use fuels::{
+    accounts::predicate::Predicate,
+    prelude::abigen,
+    types::{Address, Bits256, Bytes32},
+};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+abigen!(Predicate(
+    name = "FixedPricePredicate",
+    abi = "../nft_fixed_price_swap_predicate/out/debug/nft_fixed_price_swap_predicate-abi.json"
+));
+
+/// The bytecode of the current `nft_fixed_price_swap_predicate` release, before configurables
+pub const FIXED_PRICE_PREDICATE_CODE: &[u8] = include_bytes!(
+    "../../nft_fixed_price_swap_predicate/out/debug/nft_fixed_price_swap_predicate.bin"
+);
+
+/// The largest valid fee in basis points, a fee of the whole price
+pub const MAX_FEE_BPS: u64 = 10_000;
+
+/// A predicate bytecode release orders may refer to
+#[derive(Debug)]
+pub struct KnownPredicate {
+    pub name: &'static str,
+    pub code: &'static [u8],
+}
+
+impl KnownPredicate {
+    pub fn bytecode_hash(&self) -> Bytes32 {
+        bytecode_hash(self.code)
+    }
+
+    /// The address of this predicate with the configurables of `listing` applied
+    pub fn address(&self, listing: &ListingParams) -> Address {
+        Predicate::from_code(self.code.to_vec())
+            .with_configurables(listing.configurables())
+            .address()
+            .into()
+    }
+}
+
+/// Every predicate release orders are accepted for, older releases stay listed as long as
+/// listings made with them may still be open
+pub const KNOWN_PREDICATES: &[KnownPredicate] = &[KnownPredicate {
+    name: "nft_fixed_price_swap_predicate v1",
+    code: FIXED_PRICE_PREDICATE_CODE,
+}];
+
+/// The hash orders identify a predicate release by, sha256 of the bytecode before configurables
+pub fn bytecode_hash(code: &[u8]) -> Bytes32 {
+    Bytes32::from(<[u8; 32]>::from(Sha256::digest(code)))
+}
+
+/// The known predicate release with `bytecode_hash`
+pub fn known_predicate(bytecode_hash: Bytes32) -> Option<&'static KnownPredicate> {
+    KNOWN_PREDICATES
+        .iter()
+        .find(|predicate| predicate.bytecode_hash() == bytecode_hash)
+}
+
+impl ListingParams {
+    /// Panics when the listing has more than `MAX_EXTRA_FEE_RECIPIENTS` extra fee recipients,
+    /// `verify_order` rejects such orders first
+    pub fn configurables(&self) -> FixedPricePredicateConfigurables {
+        let mut recipients = [Bits256::zeroed(); MAX_EXTRA_FEE_RECIPIENTS];
+        let mut amounts = [0; MAX_EXTRA_FEE_RECIPIENTS];
+        let mut bps = [0; MAX_EXTRA_FEE_RECIPIENTS];
+        for (i, extra_fee) in self.extra_fees.iter().enumerate() {
+            recipients[i] = Bits256(*extra_fee.recipient);
+            amounts[i] = extra_fee.amount;
+            bps[i] = extra_fee.bps;
+        }
+
+        FixedPricePredicateConfigurables::default()
+            .with_FEE_AMOUNT(self.fee_amount)
+            .unwrap()
+            .with_FEE_ASSET(self.fee_asset)
+            .unwrap()
+            .with_TREASURY_ADDRESS(self.treasury)
+            .unwrap()
+            .with_ASK_AMOUNT(self.ask_amount)
+            .unwrap()
+            .with_ASK_ASSET(self.ask_asset)
+            .unwrap()
+            .with_RECEIVER(self.receiver)
+            .unwrap()
+            .with_NFT_ASSET_ID(self.nft_asset_id)
+            .unwrap()
+            .with_FEE_BPS(self.fee_bps)
+            .unwrap()
+            .with_EXTRA_FEE_RECIPIENT_COUNT(self.extra_fees.len() as u64)
+            .unwrap()
+            .with_EXTRA_FEE_RECIPIENTS(recipients)
+            .unwrap()
+            .with_EXTRA_FEE_AMOUNTS(amounts)
+            .unwrap()
+            .with_EXTRA_FEE_BPS(bps)
+            .unwrap()
+            .with_MAX_REFERRAL_BPS(self.max_referral_bps)
+            .unwrap()
+    }
+
+    /// The predicate of this listing in the current release, with the predicate data of a fill
+    /// without referral
+    pub fn predicate(&self) -> Predicate {
+        self.predicate_with_referrer(None)
+    }
+
+    /// The predicate with `referrer` supplied as predicate data, used to fill with a referral
+    ///
+    /// The address does not depend on the referrer, only the data does
+    pub fn predicate_with_referrer(&self, referrer: Option<Address>) -> Predicate {
+        let predicate_data = FixedPricePredicateEncoder::default()
+            .encode_data(referrer)
+            .unwrap();
+
+        Predicate::from_code(FIXED_PRICE_PREDICATE_CODE.to_vec())
+            .with_configurables(self.configurables())
+            .with_data(predicate_data)
+    }
+}
+
+impl Order {
+    /// Creates an order for `listing` locked in the current predicate release
+    pub fn from_listing(chain_id: u64, listing: ListingParams, metadata: OrderMetadata) -> Self {
+        let predicate = &KNOWN_PREDICATES[0];
+
+        Self::new(
+            chain_id,
+            predicate.bytecode_hash(),
+            predicate.address(&listing),
+            listing,
+            metadata,
+        )
+    }
+}
+
+/// Why an order must not be trusted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The order refers to a predicate release this SDK does not know
+    UnknownBytecode(Bytes32),
+    /// The order was made for another network
+    WrongChain { expected: u64, found: u64 },
+    /// The claimed address is not the predicate built from the order's configurables
+    AddressMismatch { claimed: Address, actual: Address },
+    /// The predicate rejects every fill of this listing
+    UnfillableListing(&'static str),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownBytecode(hash) => write!(f, "unknown predicate bytecode {hash}"),
+            Self::WrongChain { expected, found } => {
+                write!(f, "order is for chain {found}, expected chain {expected}")
+            }
+            Self::AddressMismatch { claimed, actual } => write!(
+                f,
+                "order claims predicate {claimed} but its configurables give {actual}"
+            ),
+            Self::UnfillableListing(reason) => write!(f, "listing cannot be filled: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Checks that `order` locks its NFT in a known predicate release with the claimed terms
+///
+/// The predicate is rebuilt from the release the order names with the order's configurables,
+/// its address must be the one the order claims
+pub fn verify_order(order: &Order, chain_id: u64) -> Result<&'static KnownPredicate, VerifyError> {
+    if order.chain_id != chain_id {
+        return Err(VerifyError::WrongChain {
+            expected: chain_id,
+            found: order.chain_id,
+        });
+    }
+
+    let predicate = known_predicate(order.predicate_bytecode_hash)
+        .ok_or(VerifyError::UnknownBytecode(order.predicate_bytecode_hash))?;
+
+    let listing = &order.listing;
+    if listing.extra_fees.len() > MAX_EXTRA_FEE_RECIPIENTS {
+        return Err(VerifyError::UnfillableListing("too many fee recipients"));
+    }
+    if listing.fee_bps > MAX_FEE_BPS || listing.extra_fees.iter().any(|fee| fee.bps > MAX_FEE_BPS) {
+        return Err(VerifyError::UnfillableListing("fee above the whole price"));
+    }
+    if listing.max_referral_bps > MAX_FEE_BPS {
+        return Err(VerifyError::UnfillableListing(
+            "referral cap above the whole price",
+        ));
+    }
+
+    let actual = predicate.address(listing);
+    if actual != order.predicate_address {
+        return Err(VerifyError::AddressMismatch {
+            claimed: order.predicate_address,
+            actual,
+        });
+    }
+
+    Ok(predicate)
+}
@@ -0,0 +1,80 @@
+//! Serde helpers encoding 32 byte ids as `0x` prefixed lowercase hex strings
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use std::ops::Deref;
+
+/// Formats `bytes` as a `0x` prefixed lowercase hex string
+pub fn encode(bytes: &[u8; 32]) -> String {
+    let mut hex = String::with_capacity(66);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    hex
+}
+
+/// Parses a hex string of 32 bytes, the `0x` prefix is optional
+pub fn decode(hex: &str) -> Option<[u8; 32]> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Serializes an id as a hex string, for `#[serde(with = "serde_hex")]`
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Deref<Target = [u8; 32]>,
+    S: Serializer,
+{
+    serializer.serialize_str(&encode(value))
+}
+
+/// Deserializes an id from a hex string, for `#[serde(with = "serde_hex")]`
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<[u8; 32]>,
+    D: Deserializer<'de>,
+{
+    let hex = String::deserialize(deserializer)?;
+    decode(&hex)
+        .map(T::from)
+        .ok_or_else(|| D::Error::custom(format!("`{hex}` is not a 32 byte hex string")))
+}
+
+/// The same for optional ids, `None` being `null`, for `#[serde(with = "serde_hex::option")]`
+pub mod option {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::ops::Deref;
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Deref<Target = [u8; 32]>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: From<[u8; 32]>,
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|hex| {
+                super::decode(&hex)
+                    .map(T::from)
+                    .ok_or_else(|| D::Error::custom(format!("`{hex}` is not a 32 byte hex string")))
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,237 @@
+//! The transactions that fill and cancel a fixed price listing
+//!
+//! The CLI and the predicate tests both build them here, so what is tested is what is sent
+
+use crate::{fee::fee_outputs, listing::ListingParams};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    prelude::{Address, AssetId, Provider, Result, TxPolicies, WalletUnlocked},
+    types::{
+        errors::Error,
+        output::Output,
+        transaction::ScriptTransaction,
+        transaction_builders::{
+            BuildableTransaction, ScriptTransactionBuilder, TransactionBuilder,
+        },
+        tx_status::TxStatus,
+    },
+};
+
+fn provider(wallet: &WalletUnlocked) -> &Provider {
+    wallet.provider().expect("wallet has a provider")
+}
+
+/// Builds the transaction that buys a listed NFT
+///
+/// The taker pays the price to the receiver and the fees to their recipients from their own
+/// coins and receives the NFT, gas is paid in the base asset. The price, the fees and the gas may
+/// each be paid in a different asset, the taker gets the change of every asset spent. A referral,
+/// if any, is paid to the referrer on top of the price so the receiver gets all of it
+pub struct FillBuilder {
+    listing: ListingParams,
+    taker: WalletUnlocked,
+    fee_asset: AssetId,
+    fee_amounts: Vec<(Address, u64)>,
+    referral: Option<(Address, u64)>,
+}
+
+impl FillBuilder {
+    pub fn new(listing: &ListingParams, taker: &WalletUnlocked) -> Self {
+        Self {
+            listing: listing.clone(),
+            taker: taker.clone(),
+            fee_asset: listing.fee_asset,
+            fee_amounts: listing.fee_amounts(),
+            referral: None,
+        }
+    }
+
+    /// Pays `fee_amount` to the treasury instead of the fee the listing asks for
+    pub fn with_fee_amount(mut self, fee_amount: u64) -> Self {
+        self.fee_amounts[0].1 = fee_amount;
+        self
+    }
+
+    /// Pays each `(recipient, amount)` in this order instead of the split the listing asks for
+    pub fn with_fee_amounts(mut self, fee_amounts: Vec<(Address, u64)>) -> Self {
+        self.fee_amounts = fee_amounts;
+        self
+    }
+
+    /// Pays the fees in `fee_asset` instead of the asset the listing asks for
+    pub fn with_fee_asset(mut self, fee_asset: AssetId) -> Self {
+        self.fee_asset = fee_asset;
+        self
+    }
+
+    /// Pays `amount` on top of the price to `referrer`, the frontend that routed the taker
+    pub fn with_referral(mut self, referrer: Address, amount: u64) -> Self {
+        self.referral = Some((referrer, amount));
+        self
+    }
+
+    /// The amount of every asset the taker pays to others, the ask asset first
+    ///
+    /// Assets shared between the price and the fees are merged into a single entry so that each
+    /// of them is covered by one coin selection. The gas is left to `adjust_for_fee`
+    fn spent_assets(&self) -> Vec<(AssetId, u64)> {
+        let total_fee: u64 = self.fee_amounts.iter().map(|(_, amount)| amount).sum();
+        let referral_amount = self.referral.map_or(0, |(_, amount)| amount);
+        let mut spent = vec![(
+            self.listing.ask_asset,
+            self.listing.ask_amount + referral_amount,
+        )];
+
+        match spent
+            .iter_mut()
+            .find(|(spent_asset, _)| *spent_asset == self.fee_asset)
+        {
+            Some((_, spent_amount)) => *spent_amount += total_fee,
+            None if total_fee > 0 => spent.push((self.fee_asset, total_fee)),
+            None => (),
+        }
+
+        spent
+    }
+
+    /// The inputs and outputs of the fill, not signed yet
+    pub async fn transaction_builder(&self) -> Result<ScriptTransactionBuilder> {
+        let provider = provider(&self.taker);
+        let base_asset = *provider.consensus_parameters().await?.base_asset_id();
+        let listing = &self.listing;
+        let predicate = listing
+            .predicate_with_referrer(self.referral.map(|(referrer, _)| referrer))
+            .with_provider(provider.clone());
+        let taker_address = Address::from(self.taker.address());
+
+        // Get predicate input
+        let input_predicate = predicate
+            .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+            .await?[0]
+            .clone();
+
+        // Get inputs from taker covering the price and the fees, the ask asset directly follows
+        // the predicate input
+        let spent_assets = self.spent_assets();
+        let mut inputs = vec![input_predicate];
+        for (asset_id, amount) in &spent_assets {
+            inputs.extend(
+                self.taker
+                    .get_asset_inputs_for_amount(*asset_id, *amount, None)
+                    .await?,
+            );
+        }
+
+        // Output for the asked coin transferred from the taker to the receiver
+        let mut outputs = vec![Output::Coin {
+            to: listing.receiver,
+            amount: listing.ask_amount,
+            asset_id: listing.ask_asset,
+        }];
+
+        // Outputs for the fee recipients, the treasury first
+        outputs.extend(fee_outputs(&self.fee_amounts, self.fee_asset));
+
+        // Output for the referral, right after the fees
+        if let Some((referrer, amount)) = self.referral {
+            outputs.push(Output::Coin {
+                to: referrer,
+                amount,
+                asset_id: listing.ask_asset,
+            });
+        }
+
+        // Output for the offered coin transferred from the predicate to the order taker
+        outputs.push(Output::Coin {
+            to: taker_address,
+            amount: 1,
+            asset_id: listing.nft_asset_id,
+        });
+
+        // Change outputs for every unspent asset, the base asset included as it pays the gas
+        let base_spent = spent_assets
+            .iter()
+            .find(|(asset_id, _)| *asset_id == base_asset)
+            .map_or(0, |(_, amount)| *amount);
+        let mut change_assets: Vec<AssetId> =
+            spent_assets.iter().map(|(asset_id, _)| *asset_id).collect();
+        if !change_assets.contains(&base_asset) {
+            change_assets.push(base_asset);
+        }
+        outputs.extend(change_assets.into_iter().map(|asset_id| Output::Change {
+            to: taker_address,
+            amount: 0,
+            asset_id,
+        }));
+
+        // Base asset coins are added until they cover what is paid in it and the real fee
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default())
+                .enable_burn(true);
+        self.taker
+            .adjust_for_fee(&mut tb, base_spent.into())
+            .await?;
+
+        Ok(tb)
+    }
+
+    /// The fill signed by the taker
+    pub async fn build(&self) -> Result<ScriptTransaction> {
+        let mut tb = self.transaction_builder().await?;
+        tb.add_signer(self.taker.clone())?;
+
+        tb.build(provider(&self.taker)).await
+    }
+
+    pub async fn submit(&self) -> Result<TxStatus> {
+        let tx = self.build().await?;
+
+        provider(&self.taker)
+            .send_transaction_and_await_commit(tx)
+            .await
+    }
+}
+
+/// The inputs and outputs of the transaction taking a listed NFT back, not signed yet
+///
+/// The predicate lets the receiver spend it when one of their own coins is the only other
+/// input, the NFT and that coin both go back to the receiver as change. That coin pays the fee,
+/// so the receiver needs a single base asset coin covering it
+pub async fn cancel_transaction_builder(
+    listing: &ListingParams,
+    receiver: &WalletUnlocked,
+) -> Result<ScriptTransactionBuilder> {
+    let provider = provider(receiver);
+    let base_asset = *provider.consensus_parameters().await?.base_asset_id();
+    let receiver_address = Address::from(receiver.address());
+    let predicate = listing.predicate().with_provider(provider.clone());
+
+    let inputs = vec![
+        predicate
+            .get_asset_inputs_for_amount(listing.nft_asset_id, 1, None)
+            .await?
+            .remove(0),
+        receiver
+            .get_asset_inputs_for_amount(base_asset, 1, None)
+            .await?
+            .remove(0),
+    ];
+    let outputs = [listing.nft_asset_id, base_asset]
+        .map(|asset_id| Output::Change {
+            to: receiver_address,
+            amount: 0,
+            asset_id,
+        })
+        .to_vec();
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    receiver.adjust_for_fee(&mut tb, 0).await?;
+    if tb.inputs().len() > 2 {
+        return Err(Error::Other(
+            "no single coin of the receiver covers the fee of the cancellation, merge coins first"
+                .to_string(),
+        ));
+    }
+
+    Ok(tb)
+}
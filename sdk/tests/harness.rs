@@ -0,0 +1,445 @@
+use fuels::types::{Address, AssetId, Bytes32};
+use nullstate_sdk::{
+    listing::{ExtraFee, ListingParams, MAX_EXTRA_FEE_RECIPIENTS},
+    order::{Order, OrderError, OrderMetadata, ORDER_VERSION},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Creates an order with every field set to a random value
+fn random_order(rng: &mut StdRng) -> Order {
+    let mut listing = ListingParams::new(AssetId::new(rng.gen()), Address::new(rng.gen()));
+    listing.ask_asset = AssetId::new(rng.gen());
+    listing.ask_amount = rng.gen();
+    listing.fee_asset = AssetId::new(rng.gen());
+    listing.treasury = Address::new(rng.gen());
+    listing.fee_amount = rng.gen();
+    listing.fee_bps = rng.gen_range(0..=10_000);
+    for _ in 0..rng.gen_range(0..=MAX_EXTRA_FEE_RECIPIENTS) {
+        listing.extra_fees.push(ExtraFee {
+            recipient: Address::new(rng.gen()),
+            amount: rng.gen(),
+            bps: rng.gen_range(0..=10_000),
+        });
+    }
+    listing.max_referral_bps = rng.gen_range(0..=10_000);
+
+    Order::new(
+        rng.gen(),
+        Bytes32::new(rng.gen()),
+        Address::new(rng.gen()),
+        listing,
+        OrderMetadata {
+            created_at: rng.gen(),
+            block_height: rng.gen(),
+            expires_at: rng.gen::<bool>().then(|| rng.gen()),
+        },
+    )
+}
+
+mod order {
+
+    use super::*;
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn json_round_trip() {
+            let mut rng = StdRng::seed_from_u64(0x6f72);
+
+            for _ in 0..1_000 {
+                let order = random_order(&mut rng);
+                let decoded = Order::from_json(&order.to_json()).unwrap();
+
+                assert_eq!(decoded, order);
+                assert_eq!(decoded.hash(), order.hash());
+            }
+        }
+
+        #[test]
+        fn one_or_many_from_json() {
+            let mut rng = StdRng::seed_from_u64(0x6f7a);
+            let orders = vec![random_order(&mut rng), random_order(&mut rng)];
+
+            assert_eq!(
+                Order::many_from_json(&serde_json::to_string(&orders).unwrap()).unwrap(),
+                orders
+            );
+            assert_eq!(
+                Order::many_from_json(&format!("\n  {}", orders[0].to_json())).unwrap(),
+                orders[..1]
+            );
+        }
+
+        #[test]
+        fn binary_round_trip() {
+            let mut rng = StdRng::seed_from_u64(0x6f73);
+
+            for _ in 0..1_000 {
+                let order = random_order(&mut rng);
+                let decoded = Order::from_bytes(&order.to_bytes()).unwrap();
+
+                assert_eq!(decoded, order);
+                assert_eq!(decoded.hash(), order.hash());
+            }
+        }
+
+        #[test]
+        fn json_and_binary_agree() {
+            let mut rng = StdRng::seed_from_u64(0x6f74);
+            let order = random_order(&mut rng);
+
+            let from_json = Order::from_json(&order.to_json()).unwrap();
+            let from_bytes = Order::from_bytes(&order.to_bytes()).unwrap();
+
+            assert_eq!(from_json.to_bytes(), from_bytes.to_bytes());
+        }
+
+        #[test]
+        fn hash_is_stable() {
+            let order = Order::new(
+                0,
+                Bytes32::zeroed(),
+                Address::zeroed(),
+                ListingParams::new(AssetId::zeroed(), Address::zeroed()),
+                OrderMetadata {
+                    created_at: 0,
+                    block_height: 0,
+                    expires_at: None,
+                },
+            );
+
+            // The version, the chain id, 7 ids, 4 listing integers, the fee count and the metadata
+            let mut expected = vec![0u8; 1 + 8 + 7 * 32 + 4 * 8 + 1 + 12];
+            expected[0] = ORDER_VERSION;
+            assert_eq!(order.to_bytes(), expected);
+
+            let json: serde_json::Value = serde_json::from_str(&order.to_json()).unwrap();
+            assert_eq!(
+                json["listing"]["nft_asset_id"],
+                format!("0x{}", "00".repeat(32))
+            );
+        }
+
+        #[test]
+        fn expiry_is_optional() {
+            let mut rng = StdRng::seed_from_u64(0x6f79);
+            let mut order = random_order(&mut rng);
+            order.metadata.expires_at = None;
+            let mut expiring = order.clone();
+            expiring.metadata.expires_at = Some(1_700_000_000);
+
+            assert_eq!(expiring.to_bytes().len(), order.to_bytes().len() + 8);
+            assert_eq!(Order::from_bytes(&expiring.to_bytes()).unwrap(), expiring);
+            assert_ne!(expiring.hash(), order.hash());
+
+            let json: serde_json::Value = serde_json::from_str(&order.to_json()).unwrap();
+            assert!(json["metadata"].get("expires_at").is_none());
+        }
+
+        #[test]
+        fn hash_changes_with_every_field() {
+            let mut rng = StdRng::seed_from_u64(0x6f75);
+            let order = random_order(&mut rng);
+
+            let mut altered = vec![];
+            let mut other = order.clone();
+            other.chain_id ^= 1;
+            altered.push(other);
+            let mut other = order.clone();
+            other.listing.ask_amount ^= 1;
+            altered.push(other);
+            let mut other = order.clone();
+            other.listing.receiver = Address::new(rng.gen());
+            altered.push(other);
+            let mut other = order.clone();
+            other.metadata.block_height ^= 1;
+            altered.push(other);
+
+            for other in altered {
+                assert_ne!(other.hash(), order.hash());
+            }
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn unknown_version() {
+            let mut rng = StdRng::seed_from_u64(0x6f76);
+            let mut bytes = random_order(&mut rng).to_bytes();
+            bytes[0] = ORDER_VERSION + 1;
+
+            assert!(matches!(
+                Order::from_bytes(&bytes),
+                Err(OrderError::UnsupportedVersion(_))
+            ));
+
+            let mut order = random_order(&mut rng);
+            order.version = ORDER_VERSION + 1;
+            assert!(matches!(
+                Order::from_json(&order.to_json()),
+                Err(OrderError::UnsupportedVersion(_))
+            ));
+        }
+
+        #[test]
+        fn truncated_or_trailing_bytes() {
+            let mut rng = StdRng::seed_from_u64(0x6f77);
+            let mut bytes = random_order(&mut rng).to_bytes();
+
+            bytes.push(0);
+            assert!(matches!(
+                Order::from_bytes(&bytes),
+                Err(OrderError::TrailingBytes(1))
+            ));
+
+            bytes.truncate(bytes.len() - 2);
+            assert!(matches!(
+                Order::from_bytes(&bytes),
+                Err(OrderError::Truncated)
+            ));
+        }
+
+        #[test]
+        fn malformed_id_in_json() {
+            let mut rng = StdRng::seed_from_u64(0x6f78);
+            let json = random_order(&mut rng)
+                .to_json()
+                .replacen("\"0x", "\"0xzz", 1);
+
+            assert!(matches!(Order::from_json(&json), Err(OrderError::Json(_))));
+        }
+
+        #[test]
+        fn malformed_order_in_json_array() {
+            let mut rng = StdRng::seed_from_u64(0x6f7b);
+            let json = format!(
+                "[{}, {}]",
+                random_order(&mut rng).to_json(),
+                random_order(&mut rng)
+                    .to_json()
+                    .replacen("\"0x", "\"0xzz", 1)
+            );
+
+            // The error is the one of the array, not of reading it as a single order
+            let error = Order::many_from_json(&json).unwrap_err().to_string();
+            assert!(error.contains("is not a 32 byte hex string"), "{error}");
+        }
+
+        #[test]
+        #[should_panic]
+        fn too_many_fee_recipients() {
+            let mut listing = ListingParams::new(AssetId::zeroed(), Address::zeroed());
+            listing.extra_fees = vec![
+                ExtraFee {
+                    recipient: Address::zeroed(),
+                    amount: 1,
+                    bps: 0,
+                };
+                MAX_EXTRA_FEE_RECIPIENTS + 1
+            ];
+
+            Order::new(
+                0,
+                Bytes32::zeroed(),
+                Address::zeroed(),
+                listing,
+                OrderMetadata {
+                    created_at: 0,
+                    block_height: 0,
+                    expires_at: None,
+                },
+            );
+        }
+    }
+}
+
+mod verify {
+
+    use super::*;
+    use nullstate_sdk::predicate::{verify_order, VerifyError, MAX_FEE_BPS};
+
+    const CHAIN_ID: u64 = 0;
+
+    // Creates an order for a random listing the predicate can be filled for
+    fn random_verified_order(rng: &mut StdRng) -> Order {
+        let mut order = random_order(rng);
+        order.listing.fee_bps = rng.gen_range(0..=MAX_FEE_BPS);
+        for extra_fee in &mut order.listing.extra_fees {
+            extra_fee.bps = rng.gen_range(0..=MAX_FEE_BPS);
+        }
+        order.listing.max_referral_bps = rng.gen_range(0..=MAX_FEE_BPS);
+
+        Order::from_listing(CHAIN_ID, order.listing, order.metadata)
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn order_from_listing() {
+            let mut rng = StdRng::seed_from_u64(0x7665);
+
+            for _ in 0..100 {
+                let order = random_verified_order(&mut rng);
+                verify_order(&order, CHAIN_ID).unwrap();
+
+                // Verification survives the exchange format
+                verify_order(&Order::from_bytes(&order.to_bytes()).unwrap(), CHAIN_ID).unwrap();
+            }
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn tampered_price() {
+            let mut rng = StdRng::seed_from_u64(0x7666);
+            let mut order = random_verified_order(&mut rng);
+            order.listing.ask_amount = order.listing.ask_amount.wrapping_sub(1);
+
+            assert!(matches!(
+                verify_order(&order, CHAIN_ID),
+                Err(VerifyError::AddressMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn tampered_receiver() {
+            let mut rng = StdRng::seed_from_u64(0x7667);
+            let mut order = random_verified_order(&mut rng);
+            order.listing.receiver = Address::new(rng.gen());
+
+            assert!(matches!(
+                verify_order(&order, CHAIN_ID),
+                Err(VerifyError::AddressMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn unknown_bytecode() {
+            let mut rng = StdRng::seed_from_u64(0x7668);
+            let mut order = random_verified_order(&mut rng);
+            order.predicate_bytecode_hash = Bytes32::new(rng.gen());
+
+            assert_eq!(
+                verify_order(&order, CHAIN_ID),
+                Err(VerifyError::UnknownBytecode(order.predicate_bytecode_hash))
+            );
+        }
+
+        #[test]
+        fn other_chain() {
+            let mut rng = StdRng::seed_from_u64(0x7669);
+            let order = random_verified_order(&mut rng);
+
+            assert_eq!(
+                verify_order(&order, CHAIN_ID + 1),
+                Err(VerifyError::WrongChain {
+                    expected: CHAIN_ID + 1,
+                    found: CHAIN_ID
+                })
+            );
+        }
+
+        #[test]
+        fn fee_above_whole_price() {
+            let mut rng = StdRng::seed_from_u64(0x766a);
+            let mut order = random_verified_order(&mut rng);
+            order.listing.fee_bps = MAX_FEE_BPS + 1;
+            let order = Order::from_listing(CHAIN_ID, order.listing, order.metadata);
+
+            assert!(matches!(
+                verify_order(&order, CHAIN_ID),
+                Err(VerifyError::UnfillableListing(_))
+            ));
+        }
+
+        #[test]
+        fn too_many_fee_recipients() {
+            let mut rng = StdRng::seed_from_u64(0x766b);
+            let mut order = random_verified_order(&mut rng);
+            let extra_fee = ExtraFee {
+                recipient: Address::new(rng.gen()),
+                amount: 1,
+                bps: 0,
+            };
+            order.listing.extra_fees = vec![extra_fee; MAX_EXTRA_FEE_RECIPIENTS + 1];
+
+            assert_eq!(
+                verify_order(&order, CHAIN_ID),
+                Err(VerifyError::UnfillableListing("too many fee recipients"))
+            );
+            assert!(order.listing.fee_split().is_err());
+        }
+    }
+}
+
+mod decode {
+
+    use super::*;
+    use fuels::accounts::predicate::Predicate;
+    use nullstate_sdk::{
+        decode::{ConfigurableLayout, DecodeError},
+        predicate::FIXED_PRICE_PREDICATE_CODE,
+    };
+
+    // The bytecode of the predicate created for `listing`
+    fn configured_code(listing: &ListingParams) -> Vec<u8> {
+        Predicate::from_code(FIXED_PRICE_PREDICATE_CODE.to_vec())
+            .with_configurables(listing.configurables())
+            .code()
+            .to_vec()
+    }
+
+    mod success {
+
+        use super::*;
+
+        #[test]
+        fn random_configurables_round_trip() {
+            let mut rng = StdRng::seed_from_u64(0x6463);
+            let layout = ConfigurableLayout::fixed_price_predicate();
+
+            for _ in 0..1_000 {
+                let listing = random_order(&mut rng).listing;
+
+                assert_eq!(
+                    layout.decode_listing(&configured_code(&listing)).unwrap(),
+                    listing
+                );
+            }
+        }
+    }
+
+    mod revert {
+
+        use super::*;
+
+        #[test]
+        fn truncated_bytecode() {
+            let layout = ConfigurableLayout::fixed_price_predicate();
+            let code = configured_code(&ListingParams::new(AssetId::zeroed(), Address::zeroed()));
+
+            assert!(matches!(
+                layout.decode_listing(&code[..code.len() / 2]),
+                Err(DecodeError::Truncated(_))
+            ));
+        }
+
+        #[test]
+        fn abi_without_configurables() {
+            assert!(matches!(
+                ConfigurableLayout::from_abi_json(r#"{ "concreteTypes": [] }"#),
+                Err(DecodeError::InvalidAbi(_))
+            ));
+        }
+    }
+}